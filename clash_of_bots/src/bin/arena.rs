@@ -0,0 +1,812 @@
+#![allow(clippy::needless_return, clippy::upper_case_acronyms)]
+
+// plays two bot binaries against each other offline, every turn both bots get the 5x5 view of
+// each of their robots in the same format the game sends and answer with one command per robot,
+// attacks and self destructions are resolved first, then the survivors move
+//
+// usage: arena [--seed S] [--games N] [--max-turns T] [--turn-timeout MS] [--verbose]
+//              "<bot 1>" "<bot 2>"
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+static ARENA_WIDTH: i8 = 13;
+static ARENA_HEIGHT: i8 = 13;
+
+// robots start in the columns on their own side, player 2's setup is player 1's turned around
+static NUM_OF_START_COLUMNS: i8 = 3;
+static NUM_OF_ROBOTS_PER_PLAYER: usize = 6;
+static ROBOT_START_HEALTH: i8 = 10;
+
+static ATTACK_DAMAGE: i8 = 2;
+static SELF_DESTRUCTION_DAMAGE: i8 = 4;
+static COLLISION_DAMAGE: i8 = 1;
+
+static LOCAL_AREA_RADIUS: i8 = 2;
+static MAX_NUM_OF_TURNS: usize = 200;
+
+// generous for a local run, debug builds included, a bot that goes past it forfeits
+static TURN_TIMEOUT_MS: u64 = 1000;
+
+static NUM_OF_PLAYERS: usize = 2;
+
+static USAGE: &str = "usage: arena [--seed S] [--games N] [--max-turns T] [--turn-timeout MS] \
+                      [--verbose] <bot 1> <bot 2>";
+
+static NEIGHBOURING_AREA: [[i8; 2]; 8] = [
+    [-1, -1],
+    [0, -1],
+    [1, -1],
+    [-1, 0],
+    [1, 0],
+    [-1, 1],
+    [0, 1],
+    [1, 1],
+];
+
+#[derive(Debug)]
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn new(seed: u64) -> Random {
+        return Random {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        };
+    }
+
+    fn next_below(&mut self, upper_bound: usize) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return (self.state % upper_bound as u64) as usize;
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Direction {
+    UP,
+    DOWN,
+    LEFT,
+    RIGHT,
+}
+
+impl Direction {
+    fn from_str(value: &str) -> Option<Direction> {
+        return match value {
+            "UP" => Some(Direction::UP),
+            "DOWN" => Some(Direction::DOWN),
+            "LEFT" => Some(Direction::LEFT),
+            "RIGHT" => Some(Direction::RIGHT),
+            _ => None,
+        };
+    }
+
+    fn to_vector(self) -> [i8; 2] {
+        return match self {
+            Direction::UP => [0, -1],
+            Direction::DOWN => [0, 1],
+            Direction::LEFT => [-1, 0],
+            Direction::RIGHT => [1, 0],
+        };
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum RobotCommand {
+    MOVE(Direction),
+    ATTACK(Direction),
+    GUARD,
+    SELFDESTRUCTION,
+}
+
+impl RobotCommand {
+    // anything after the command itself is a message and ignored
+    fn from_str(line: &str) -> Option<RobotCommand> {
+        let mut tokens = line.split_whitespace();
+        return match tokens.next() {
+            Some("MOVE") => Some(RobotCommand::MOVE(Direction::from_str(tokens.next()?)?)),
+            Some("ATTACK") => Some(RobotCommand::ATTACK(Direction::from_str(tokens.next()?)?)),
+            Some("GUARD") => Some(RobotCommand::GUARD),
+            Some("SELFDESTRUCTION") => Some(RobotCommand::SELFDESTRUCTION),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Robot {
+    player_id: usize,
+    health: i8,
+    x: i8,
+    y: i8,
+}
+
+#[derive(Debug)]
+struct Arena {
+    robots: Vec<Robot>,
+    num_of_turns: usize,
+}
+
+impl Arena {
+    fn new(random: &mut Random) -> Arena {
+        let mut cells: Vec<[i8; 2]> = vec![];
+        for x in 0..NUM_OF_START_COLUMNS {
+            for y in 0..ARENA_HEIGHT {
+                cells.push([x, y]);
+            }
+        }
+        let mut robots: Vec<Robot> = vec![];
+        for _ in 0..NUM_OF_ROBOTS_PER_PLAYER {
+            let [x, y] = cells.swap_remove(random.next_below(cells.len()));
+            for player_id in 0..NUM_OF_PLAYERS {
+                let (x, y) = if player_id == 0 {
+                    (x, y)
+                } else {
+                    (ARENA_WIDTH - 1 - x, ARENA_HEIGHT - 1 - y)
+                };
+                robots.push(Robot {
+                    player_id,
+                    health: ROBOT_START_HEALTH,
+                    x,
+                    y,
+                });
+            }
+        }
+        return Arena {
+            robots,
+            num_of_turns: 0,
+        };
+    }
+
+    fn is_inside(x: i8, y: i8) -> bool {
+        return x >= 0 && x < ARENA_WIDTH && y >= 0 && y < ARENA_HEIGHT;
+    }
+
+    fn get_robot_index_at(&self, x: i8, y: i8) -> Option<usize> {
+        return self.robots.iter().position(|v| v.x == x && v.y == y);
+    }
+
+    // the order the robots are listed to their player, and the order the commands come back in
+    fn get_player_robot_indexes(&self, player_id: usize) -> Vec<usize> {
+        return (0..self.robots.len())
+            .filter(|v| self.robots[*v].player_id == player_id)
+            .collect();
+    }
+
+    fn get_player_health(&self, player_id: usize) -> i32 {
+        return self
+            .robots
+            .iter()
+            .filter(|v| v.player_id == player_id)
+            .map(|v| v.health as i32)
+            .sum();
+    }
+
+    // friendly robots are positive, enemies negative, empty cells and cells off the map are 0
+    fn get_cell_value(&self, x: i8, y: i8, player_id: usize) -> i8 {
+        return match self.get_robot_index_at(x, y) {
+            Some(robot_index) if self.robots[robot_index].player_id == player_id => {
+                self.robots[robot_index].health
+            }
+            Some(robot_index) => -self.robots[robot_index].health,
+            None => 0,
+        };
+    }
+
+    fn get_local_area_input(&self, robot: &Robot) -> String {
+        let mut lines: Vec<String> = vec![];
+        for dy in -LOCAL_AREA_RADIUS..=LOCAL_AREA_RADIUS {
+            let values: Vec<String> = (-LOCAL_AREA_RADIUS..=LOCAL_AREA_RADIUS)
+                .map(|dx| {
+                    self.get_cell_value(robot.x + dx, robot.y + dy, robot.player_id)
+                        .to_string()
+                })
+                .collect();
+            lines.push(values.join(" "));
+        }
+        return lines.join("\n");
+    }
+
+    fn get_player_input(&self, player_id: usize) -> String {
+        let robot_indexes = self.get_player_robot_indexes(player_id);
+        let mut input = format!("{}\n", robot_indexes.len());
+        for robot_index in robot_indexes.iter() {
+            input += &self.get_local_area_input(&self.robots[*robot_index]);
+            input += "\n";
+        }
+        return input;
+    }
+
+    fn apply_commands(&mut self, commands: &[RobotCommand]) {
+        let mut damages: Vec<i8> = vec![0; self.robots.len()];
+        for (robot_index, command) in commands.iter().enumerate() {
+            let robot = &self.robots[robot_index];
+            match command {
+                RobotCommand::ATTACK(direction) => {
+                    let [dx, dy] = direction.to_vector();
+                    if let Some(target_index) = self.get_robot_index_at(robot.x + dx, robot.y + dy)
+                    {
+                        damages[target_index] += ATTACK_DAMAGE;
+                    }
+                }
+                RobotCommand::SELFDESTRUCTION => {
+                    for [dx, dy] in NEIGHBOURING_AREA.iter() {
+                        if let Some(target_index) =
+                            self.get_robot_index_at(robot.x + dx, robot.y + dy)
+                        {
+                            damages[target_index] += SELF_DESTRUCTION_DAMAGE;
+                        }
+                    }
+                    damages[robot_index] += robot.health;
+                }
+                _ => {}
+            }
+        }
+        for (robot_index, command) in commands.iter().enumerate() {
+            // guarding halves whatever hits the robot, but not its own self destruction
+            let damage = if *command == RobotCommand::GUARD {
+                damages[robot_index] / 2
+            } else {
+                damages[robot_index]
+            };
+            self.robots[robot_index].health -= damage;
+        }
+
+        let num_of_robots = self.robots.len();
+        let mut targets: Vec<Option<[i8; 2]>> = vec![None; num_of_robots];
+        for (robot_index, command) in commands.iter().enumerate() {
+            let robot = &self.robots[robot_index];
+            if let (RobotCommand::MOVE(direction), true) = (command, robot.health > 0) {
+                let [dx, dy] = direction.to_vector();
+                if Arena::is_inside(robot.x + dx, robot.y + dy) {
+                    targets[robot_index] = Some([robot.x + dx, robot.y + dy]);
+                }
+            }
+        }
+        let mut is_blocked: Vec<bool> = vec![false; num_of_robots];
+        for robot_index in 0..num_of_robots {
+            for other_robot_index in 0..num_of_robots {
+                if robot_index == other_robot_index || targets[robot_index].is_none() {
+                    continue;
+                }
+                let robot = &self.robots[robot_index];
+                let other_robot = &self.robots[other_robot_index];
+                let is_contested = targets[robot_index] == targets[other_robot_index];
+                let is_swapping = targets[robot_index] == Some([other_robot.x, other_robot.y])
+                    && targets[other_robot_index] == Some([robot.x, robot.y]);
+                if is_contested || is_swapping {
+                    is_blocked[robot_index] = true;
+                }
+            }
+        }
+        // a robot that stays where it is blocks whoever wanted its cell, which may leave another
+        // robot behind in turn, so this runs until nothing changes
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for robot_index in 0..num_of_robots {
+                let [x, y] = match targets[robot_index] {
+                    Some(target) if !is_blocked[robot_index] => target,
+                    _ => continue,
+                };
+                let is_target_kept = self.robots.iter().enumerate().any(|(other_index, v)| {
+                    v.x == x
+                        && v.y == y
+                        && v.health > 0
+                        && (targets[other_index].is_none() || is_blocked[other_index])
+                });
+                if is_target_kept {
+                    is_blocked[robot_index] = true;
+                    is_changed = true;
+                }
+            }
+        }
+        for robot_index in 0..num_of_robots {
+            match targets[robot_index] {
+                Some(_) if is_blocked[robot_index] => {
+                    self.robots[robot_index].health -= COLLISION_DAMAGE;
+                }
+                Some([x, y]) => {
+                    self.robots[robot_index].x = x;
+                    self.robots[robot_index].y = y;
+                }
+                None => {}
+            }
+        }
+
+        self.robots.retain(|v| v.health > 0);
+        self.num_of_turns += 1;
+    }
+
+    // Some(None) is a draw, None means the game goes on
+    fn get_winner(&self, max_num_of_turns: usize) -> Option<Option<usize>> {
+        let num_of_robots: Vec<usize> = (0..NUM_OF_PLAYERS)
+            .map(|player_id| self.get_player_robot_indexes(player_id).len())
+            .collect();
+        let is_over = num_of_robots.contains(&0) || self.num_of_turns >= max_num_of_turns;
+        if !is_over {
+            return None;
+        }
+        let scores: Vec<(usize, i32)> = (0..NUM_OF_PLAYERS)
+            .map(|player_id| (num_of_robots[player_id], self.get_player_health(player_id)))
+            .collect();
+        if scores[0] == scores[1] {
+            return Some(None);
+        }
+        return Some(Some(if scores[0] > scores[1] { 0 } else { 1 }));
+    }
+
+    // from player 1's point of view, like the bots see it
+    fn print(&self) {
+        for y in 0..ARENA_HEIGHT {
+            let values: Vec<String> = (0..ARENA_WIDTH)
+                .map(|x| format!("{:>3}", self.get_cell_value(x, y, 0)))
+                .collect();
+            eprintln!("{}", values.join(""));
+        }
+    }
+}
+
+// the output is read on its own thread so that a bot that hangs cannot hang the arena with it
+struct Bot {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl Bot {
+    // run through the shell so a bot can be given arguments, e.g. "python3 bot.py"
+    fn new(command_line: &str, is_verbose: bool) -> Result<Bot, String> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command_line)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(if is_verbose {
+                Stdio::inherit()
+            } else {
+                Stdio::null()
+            })
+            .spawn()
+            .map_err(|error| format!("could not start {}: {}", command_line, error))?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    return;
+                }
+            }
+        });
+        return Ok(Bot {
+            child,
+            stdin,
+            lines,
+        });
+    }
+
+    // lines a bot printed beyond one per robot would be read as the next turn's commands and put
+    // every later turn out of step, they are reported and dropped instead
+    fn drop_extra_lines(&mut self) {
+        let mut extra_lines: Vec<String> = vec![];
+        while let Ok(line) = self.lines.try_recv() {
+            extra_lines.push(line);
+        }
+        if !extra_lines.is_empty() {
+            eprintln!(
+                "ignoring {} extra lines, the bot answers one per robot: {:?}",
+                extra_lines.len(),
+                extra_lines
+            );
+        }
+    }
+
+    // a command that does not parse is played as GUARD, a bot that closes its output or does not
+    // answer every robot within the turn timeout forfeits, extra lines are dropped both before the
+    // turn and after it, whenever they arrive
+    fn play_turn(
+        &mut self,
+        input: &str,
+        num_of_robots: usize,
+        turn_timeout: Duration,
+    ) -> Result<Vec<RobotCommand>, String> {
+        self.drop_extra_lines();
+        self.stdin
+            .write_all(input.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|error| format!("could not write to bot: {}", error))?;
+        let deadline = Instant::now() + turn_timeout;
+        let mut commands: Vec<RobotCommand> = vec![];
+        for _ in 0..num_of_robots {
+            let time_left = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(time_left) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!(
+                        "bot did not answer within {}ms",
+                        turn_timeout.as_millis()
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("bot stopped answering".to_string())
+                }
+            };
+            commands.push(RobotCommand::from_str(&line).unwrap_or_else(|| {
+                eprintln!("invalid command {:?}, playing GUARD", line.trim());
+                RobotCommand::GUARD
+            }));
+        }
+        self.drop_extra_lines();
+        return Ok(commands);
+    }
+}
+
+impl Drop for Bot {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn play_game(
+    bot_command_lines: &[String],
+    random: &mut Random,
+    max_num_of_turns: usize,
+    turn_timeout: Duration,
+    is_verbose: bool,
+) -> Result<(Option<usize>, Arena), String> {
+    let mut bots: Vec<Bot> = vec![];
+    for command_line in bot_command_lines.iter() {
+        bots.push(Bot::new(command_line, is_verbose)?);
+    }
+    let mut arena = Arena::new(random);
+    loop {
+        if let Some(winner) = arena.get_winner(max_num_of_turns) {
+            return Ok((winner, arena));
+        }
+        let mut commands: Vec<RobotCommand> = vec![RobotCommand::GUARD; arena.robots.len()];
+        for (player_id, bot) in bots.iter_mut().enumerate() {
+            let robot_indexes = arena.get_player_robot_indexes(player_id);
+            let input = arena.get_player_input(player_id);
+            match bot.play_turn(&input, robot_indexes.len(), turn_timeout) {
+                Ok(player_commands) => {
+                    for (robot_index, command) in robot_indexes.iter().zip(player_commands) {
+                        commands[*robot_index] = command;
+                    }
+                }
+                Err(error) => {
+                    eprintln!("player {}: {}", player_id + 1, error);
+                    return Ok((Some(1 - player_id), arena));
+                }
+            }
+        }
+        arena.apply_commands(&commands);
+        if is_verbose {
+            eprintln!("turn {}", arena.num_of_turns);
+            arena.print();
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ArenaOptions {
+    seed: u64,
+    num_of_games: usize,
+    max_num_of_turns: usize,
+    turn_timeout_ms: u64,
+    is_verbose: bool,
+    bot_command_lines: Vec<String>,
+}
+
+// a value that is missing or does not parse is an error rather than the default, so a run never
+// quietly plays something other than what was asked for
+fn parse_arena_options(args: &[String]) -> Result<ArenaOptions, String> {
+    let mut options = ArenaOptions {
+        seed: 1,
+        num_of_games: 1,
+        max_num_of_turns: MAX_NUM_OF_TURNS,
+        turn_timeout_ms: TURN_TIMEOUT_MS,
+        is_verbose: false,
+        bot_command_lines: vec![],
+    };
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut get_value = || {
+            let value = args_iter
+                .next()
+                .ok_or_else(|| format!("{} expects a number", arg))?;
+            return value
+                .parse::<u64>()
+                .map_err(|_| format!("{} expects a number, found {}", arg, value));
+        };
+        match arg.as_str() {
+            "--seed" => options.seed = get_value()?,
+            "--games" => options.num_of_games = get_value()? as usize,
+            "--max-turns" => options.max_num_of_turns = get_value()? as usize,
+            "--turn-timeout" => options.turn_timeout_ms = get_value()?,
+            "--verbose" => options.is_verbose = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ => options.bot_command_lines.push(arg.clone()),
+        }
+    }
+    if options.bot_command_lines.len() != NUM_OF_PLAYERS {
+        return Err(format!(
+            "expected {} bots, found {}",
+            NUM_OF_PLAYERS,
+            options.bot_command_lines.len()
+        ));
+    }
+    return Ok(options);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let ArenaOptions {
+        seed,
+        num_of_games,
+        max_num_of_turns,
+        turn_timeout_ms,
+        is_verbose,
+        bot_command_lines,
+    } = match parse_arena_options(&args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let mut random = Random::new(seed);
+    let mut num_of_wins: [usize; 2] = [0; 2];
+    let mut num_of_draws: usize = 0;
+    for game_index in 0..num_of_games {
+        let (winner, arena) = match play_game(
+            &bot_command_lines,
+            &mut random,
+            max_num_of_turns,
+            Duration::from_millis(turn_timeout_ms),
+            is_verbose,
+        ) {
+            Ok(result) => result,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        };
+        let outcome = match winner {
+            Some(player_id) => {
+                num_of_wins[player_id] += 1;
+                format!("player {} wins", player_id + 1)
+            }
+            None => {
+                num_of_draws += 1;
+                "draw".to_string()
+            }
+        };
+        println!(
+            "game {}: {} after {} turns, robots {} vs {}, health {} vs {}",
+            game_index + 1,
+            outcome,
+            arena.num_of_turns,
+            arena.get_player_robot_indexes(0).len(),
+            arena.get_player_robot_indexes(1).len(),
+            arena.get_player_health(0),
+            arena.get_player_health(1)
+        );
+    }
+    println!(
+        "player 1: {}, player 2: {}, draws: {}",
+        num_of_wins[0], num_of_wins[1], num_of_draws
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_arena(robots: &[(usize, i8, i8, i8)]) -> Arena {
+        return Arena {
+            robots: robots
+                .iter()
+                .map(|(player_id, health, x, y)| Robot {
+                    player_id: *player_id,
+                    health: *health,
+                    x: *x,
+                    y: *y,
+                })
+                .collect(),
+            num_of_turns: 0,
+        };
+    }
+
+    fn get_robot_states(arena: &Arena) -> Vec<(i8, i8, i8)> {
+        return arena.robots.iter().map(|v| (v.health, v.x, v.y)).collect();
+    }
+
+    #[test]
+    fn attack_damages_the_target_and_guarding_halves_it() {
+        let mut arena = create_arena(&[(0, 10, 5, 5), (1, 10, 6, 5), (1, 10, 5, 6)]);
+        arena.apply_commands(&[
+            RobotCommand::ATTACK(Direction::RIGHT),
+            RobotCommand::GUARD,
+            RobotCommand::ATTACK(Direction::UP),
+        ]);
+        assert_eq!(
+            get_robot_states(&arena),
+            vec![(8, 5, 5), (9, 6, 5), (10, 5, 6)]
+        );
+    }
+
+    #[test]
+    fn attack_into_an_empty_cell_does_nothing() {
+        let mut arena = create_arena(&[(0, 10, 5, 5), (1, 10, 7, 5)]);
+        arena.apply_commands(&[RobotCommand::ATTACK(Direction::RIGHT), RobotCommand::GUARD]);
+        assert_eq!(get_robot_states(&arena), vec![(10, 5, 5), (10, 7, 5)]);
+    }
+
+    #[test]
+    fn self_destruction_hits_every_neighbour_and_removes_the_robot() {
+        let mut arena = create_arena(&[
+            (0, 10, 5, 5),
+            (1, 10, 4, 4),
+            (1, 10, 6, 6),
+            (0, 10, 5, 6),
+            (1, 10, 7, 5),
+        ]);
+        arena.apply_commands(&[
+            RobotCommand::SELFDESTRUCTION,
+            RobotCommand::GUARD,
+            RobotCommand::GUARD,
+            RobotCommand::GUARD,
+            RobotCommand::GUARD,
+        ]);
+        assert_eq!(
+            get_robot_states(&arena),
+            vec![(8, 4, 4), (8, 6, 6), (8, 5, 6), (10, 7, 5)]
+        );
+    }
+
+    #[test]
+    fn robot_killed_by_damage_does_not_move_and_frees_its_cell() {
+        let mut arena = create_arena(&[(0, 2, 5, 5), (1, 10, 6, 5), (1, 10, 4, 5)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::UP),
+            RobotCommand::ATTACK(Direction::LEFT),
+            RobotCommand::MOVE(Direction::RIGHT),
+        ]);
+        assert_eq!(get_robot_states(&arena), vec![(10, 6, 5), (10, 5, 5)]);
+    }
+
+    #[test]
+    fn robots_moving_into_the_same_cell_both_collide() {
+        let mut arena = create_arena(&[(0, 10, 4, 5), (1, 10, 6, 5)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::MOVE(Direction::LEFT),
+        ]);
+        assert_eq!(get_robot_states(&arena), vec![(9, 4, 5), (9, 6, 5)]);
+    }
+
+    #[test]
+    fn robots_swapping_cells_both_collide() {
+        let mut arena = create_arena(&[(0, 10, 5, 5), (1, 10, 6, 5)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::MOVE(Direction::LEFT),
+        ]);
+        assert_eq!(get_robot_states(&arena), vec![(9, 5, 5), (9, 6, 5)]);
+    }
+
+    #[test]
+    fn robot_staying_put_blocks_the_whole_chain_behind_it() {
+        let mut arena = create_arena(&[(0, 10, 3, 5), (0, 10, 4, 5), (1, 10, 5, 5)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::GUARD,
+        ]);
+        assert_eq!(
+            get_robot_states(&arena),
+            vec![(9, 3, 5), (9, 4, 5), (10, 5, 5)]
+        );
+    }
+
+    #[test]
+    fn chain_moves_along_when_its_front_robot_moves() {
+        let mut arena = create_arena(&[(0, 10, 3, 5), (0, 10, 4, 5), (0, 10, 5, 5)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::MOVE(Direction::RIGHT),
+            RobotCommand::MOVE(Direction::RIGHT),
+        ]);
+        assert_eq!(
+            get_robot_states(&arena),
+            vec![(10, 4, 5), (10, 5, 5), (10, 6, 5)]
+        );
+    }
+
+    #[test]
+    fn move_off_the_map_is_ignored() {
+        let mut arena = create_arena(&[(0, 10, 0, 0), (1, 10, 12, 12)]);
+        arena.apply_commands(&[
+            RobotCommand::MOVE(Direction::UP),
+            RobotCommand::MOVE(Direction::RIGHT),
+        ]);
+        assert_eq!(get_robot_states(&arena), vec![(10, 0, 0), (10, 12, 12)]);
+        assert_eq!(arena.num_of_turns, 1);
+    }
+
+    fn get_args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|v| v.to_string()).collect();
+    }
+
+    #[test]
+    fn options_are_read_or_rejected() {
+        assert_eq!(
+            parse_arena_options(&get_args(&[
+                "--seed",
+                "7",
+                "--games",
+                "3",
+                "--turn-timeout",
+                "200",
+                "--verbose",
+                "bot a",
+                "bot b"
+            ])),
+            Ok(ArenaOptions {
+                seed: 7,
+                num_of_games: 3,
+                max_num_of_turns: MAX_NUM_OF_TURNS,
+                turn_timeout_ms: 200,
+                is_verbose: true,
+                bot_command_lines: get_args(&["bot a", "bot b"]),
+            })
+        );
+        for (args, error) in [
+            (
+                &["--seed", "abc", "a", "b"][..],
+                "--seed expects a number, found abc",
+            ),
+            (
+                &["--games", "x", "a", "b"][..],
+                "--games expects a number, found x",
+            ),
+            (
+                &["a", "b", "--max-turns"][..],
+                "--max-turns expects a number",
+            ),
+            (&["--fast", "a", "b"][..], "unknown option --fast"),
+            (&["a"][..], "expected 2 bots, found 1"),
+        ]
+        .iter()
+        {
+            assert_eq!(parse_arena_options(&get_args(args)), Err(error.to_string()));
+        }
+    }
+
+    #[test]
+    fn extra_bot_lines_do_not_carry_over_to_the_next_turn() {
+        // answers the first turn with a line too many, then stops answering
+        let mut bot = Bot::new("read n; echo 'MOVE UP'; echo GUARD; sleep 5", false).unwrap();
+        let turn_timeout = Duration::from_millis(500);
+        let commands = bot.play_turn("1\n", 1, turn_timeout);
+        assert_eq!(commands, Ok(vec![RobotCommand::MOVE(Direction::UP)]));
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(
+            bot.play_turn("1\n", 1, turn_timeout),
+            Err("bot did not answer within 500ms".to_string())
+        );
+    }
+}
@@ -1,5 +1,10 @@
+#![allow(clippy::needless_return, clippy::upper_case_acronyms)]
+
 use std::collections::HashMap;
+use std::env;
+use std::fs;
 use std::io;
+use std::process;
 use std::slice::Iter;
 
 macro_rules! parse_input {
@@ -23,6 +28,7 @@ impl Coordinates {
         return Coordinates::new(self.x + vector.x, self.y + vector.y);
     }
 
+    #[allow(dead_code)]
     fn manhattan_distance_from(&self, other: &Coordinates) -> i8 {
         return (other.x.abs() - self.x.abs()).abs() + (other.y.abs() - self.y.abs()).abs();
     }
@@ -39,6 +45,7 @@ impl Vector {
         return Vector { x, y };
     }
 
+    #[allow(dead_code)]
     fn to_enum(&self) -> Direction {
         return match self {
             Vector { x: 0, y: -1 } => Direction::UP,
@@ -59,7 +66,7 @@ enum Command {
 }
 
 impl Command {
-    fn to_str(&self) -> &'static str {
+    fn to_str(self) -> &'static str {
         return match self {
             Command::MOVE => "MOVE",
             Command::ATTACK => "ATTACK",
@@ -78,7 +85,7 @@ enum Direction {
 }
 
 impl Direction {
-    fn to_str(&self) -> &'static str {
+    fn to_str(self) -> &'static str {
         return match self {
             Direction::UP => "UP",
             Direction::DOWN => "DOWN",
@@ -87,13 +94,12 @@ impl Direction {
         };
     }
 
-    fn to_vector(&self) -> Vector {
+    fn to_vector(self) -> Vector {
         return match self {
             Direction::UP => Vector { x: 0, y: -1 },
             Direction::DOWN => Vector { x: 0, y: 1 },
             Direction::LEFT => Vector { x: -1, y: 0 },
             Direction::RIGHT => Vector { x: 1, y: 0 },
-            _ => panic!("Not handled direction"),
         };
     }
 
@@ -108,6 +114,7 @@ impl Direction {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Robot {
     id: usize,
@@ -180,55 +187,48 @@ impl LocalArea {
     fn is_friendly_at_coords(&self, coordinates: &Coordinates) -> bool {
         return self.get_robot_health_at_coords(coordinates) > 0;
     }
-}
-
-#[derive(Debug, PartialEq, Eq, Hash)]
-enum ConditionName {
-    AmIAlone,
-    AmIInDanger,
-    DefaultTrue,
-    HasEnemyNextToMe,
-    WouldSelfDestructWorthIt,
-}
 
-trait Condition {
-    fn is_true(&self, local_area: &LocalArea) -> bool;
-}
-
-type ConditionDictionary = HashMap<ConditionName, Box<dyn Condition>>;
-
-struct DefaultTrue {}
-
-impl Condition for DefaultTrue {
-    fn is_true(&self, _: &LocalArea) -> bool {
-        return true;
+    fn get_my_health(&self) -> i8 {
+        return self.get_robot_health_at_coords(&Coordinates::new(2, 2));
     }
-}
 
-struct AmIInDanger {}
-
-impl Condition for AmIInDanger {
-    fn is_true(&self, local_area: &LocalArea) -> bool {
+    // up, left, down and right of the robot
+    fn get_num_of_robots_next_to_me(&self, is_friendly: bool) -> i8 {
         let robot_coordinates = Coordinates::new(2, 2);
-        let mut num_of_enemies_next_to_me: u8 = 0;
+        let mut num_of_robots_next_to_me: i8 = 0;
         for direction in Direction::iterator() {
             let vector = direction.to_vector();
             let neighbouring_cell = robot_coordinates.add_vector(&vector);
-            if local_area.is_enemy_at_coords(&neighbouring_cell) {
-                num_of_enemies_next_to_me += 1;
+            let is_matching = if is_friendly {
+                self.is_friendly_at_coords(&neighbouring_cell)
+            } else {
+                self.is_enemy_at_coords(&neighbouring_cell)
+            };
+            if is_matching {
+                num_of_robots_next_to_me += 1;
             }
         }
-        if num_of_enemies_next_to_me >= 2 {
-            return true;
-        }
-        return false;
+        return num_of_robots_next_to_me;
     }
-}
 
-struct WouldSelfDestructWorthIt {}
+    // anywhere in the square of the given radius around the robot, the robot itself not included
+    fn get_num_of_robots_around_me(&self, is_friendly: bool, radius: i8) -> i8 {
+        let robot_coordinates = Coordinates::new(2, 2);
+        let num_of_robots_around_me = self
+            .robots
+            .iter()
+            .filter(|v| v.is_friendly == is_friendly)
+            .filter(|v| {
+                let x_distance = (v.coordinates.x - robot_coordinates.x).abs();
+                let y_distance = (v.coordinates.y - robot_coordinates.y).abs();
+                return x_distance.max(y_distance) <= radius && (x_distance, y_distance) != (0, 0);
+            })
+            .count();
+        return num_of_robots_around_me as i8;
+    }
 
-impl Condition for WouldSelfDestructWorthIt {
-    fn is_true(&self, local_area: &LocalArea) -> bool {
+    // enemies a self destruction would destroy minus the friendly robots it would, itself included
+    fn get_self_destruction_balance(&self, max_health_destroyed: i8) -> i8 {
         let robot_coordinates = Coordinates::new(2, 2);
         let mut damage_caused_to_enemy: i8 = 0;
         let mut damage_caused_to_own: i8 = 1;
@@ -236,12 +236,12 @@ impl Condition for WouldSelfDestructWorthIt {
             let [x, y] = *relative_coordinates_;
             let vector = Vector::new(x, y);
             let cell_being_scanned = robot_coordinates.add_vector(&vector);
-            let robot_health_at_coords = local_area.get_robot_health_at_coords(&cell_being_scanned);
+            let robot_health_at_coords = self.get_robot_health_at_coords(&cell_being_scanned);
             if robot_health_at_coords == 0 {
                 continue;
             }
             let is_enemy = robot_health_at_coords < 0;
-            if robot_health_at_coords.abs() <= 4 {
+            if robot_health_at_coords.abs() <= max_health_destroyed {
                 if is_enemy {
                     damage_caused_to_enemy += 1;
                 } else {
@@ -249,39 +249,265 @@ impl Condition for WouldSelfDestructWorthIt {
                 }
             }
         }
-        return damage_caused_to_enemy > damage_caused_to_own;
+        return damage_caused_to_enemy - damage_caused_to_own;
+    }
+}
+
+// the damage a self destruction deals to every robot around it
+static SELF_DESTRUCTION_DAMAGE: i8 = 4;
+
+// the largest square around the robot its 5x5 local area covers
+static MAX_AREA_RADIUS: i8 = 2;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+enum Comparison {
+    Below(i8),
+    AtMost(i8),
+    Exactly(i8),
+    AtLeast(i8),
+    Above(i8),
+}
+
+impl Comparison {
+    // ">=3", "<=1", "==0", "<2" or ">2"
+    fn from_str(value: &str) -> Option<Comparison> {
+        for (operator, create_comparison) in [
+            (">=", Comparison::AtLeast as fn(i8) -> Comparison),
+            ("<=", Comparison::AtMost),
+            ("==", Comparison::Exactly),
+            ("<", Comparison::Below),
+            (">", Comparison::Above),
+        ]
+        .iter()
+        {
+            if let Some(number) = value.strip_prefix(operator) {
+                return number.trim().parse::<i8>().ok().map(create_comparison);
+            }
+        }
+        return None;
+    }
+
+    fn is_true(self, value: i8) -> bool {
+        return match self {
+            Comparison::Below(number) => value < number,
+            Comparison::AtMost(number) => value <= number,
+            Comparison::Exactly(number) => value == number,
+            Comparison::AtLeast(number) => value >= number,
+            Comparison::Above(number) => value > number,
+        };
+    }
+}
+
+// adjacent means up, left, down or right of the robot, within a radius means anywhere in the
+// square of that radius around it, the robot itself never counts
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum ConditionName {
+    AmIAlone,
+    AmIInDanger,
+    DefaultTrue,
+    HasEnemyNextToMe,
+    WouldSelfDestructWorthIt(i8),
+    EnemiesAdjacent(Comparison),
+    FriendsAdjacent(Comparison),
+    EnemiesWithin(i8, Comparison),
+    FriendsWithin(i8, Comparison),
+    MyHealthBelow(i8),
+}
+
+impl ConditionName {
+    // "EnemiesAdjacent(>=3)", "FriendsWithin(2, ==0)", "MyHealthBelow(3)", the conditions without
+    // parameters keep their meaning, WouldSelfDestructWorthIt optionally takes the health up to
+    // which a robot next to the blast counts as destroyed
+    fn from_str(name: &str, parameters: &[String]) -> Result<ConditionName, String> {
+        let get_number = |parameter: &String| {
+            return parameter
+                .parse::<i8>()
+                .map_err(|_| format!("{} expects a number instead of {}", name, parameter));
+        };
+        let get_comparison = |parameter: &String| {
+            return Comparison::from_str(parameter).ok_or_else(|| {
+                format!(
+                    "{} expects a comparison like >=2 instead of {}",
+                    name, parameter
+                )
+            });
+        };
+        let get_radius = |parameter: &String| {
+            let radius = get_number(parameter)?;
+            if radius < 1 || radius > MAX_AREA_RADIUS {
+                return Err(format!(
+                    "{} expects a radius from 1 to {}",
+                    name, MAX_AREA_RADIUS
+                ));
+            }
+            return Ok(radius);
+        };
+        let check_num_of_parameters = |min: usize, max: usize| {
+            if parameters.len() < min || parameters.len() > max {
+                return Err(format!(
+                    "wrong number of parameters for {}: {}",
+                    name,
+                    parameters.len()
+                ));
+            }
+            return Ok(());
+        };
+        return match name {
+            "AmIAlone" => check_num_of_parameters(0, 0).map(|_| ConditionName::AmIAlone),
+            "AmIInDanger" => check_num_of_parameters(0, 0).map(|_| ConditionName::AmIInDanger),
+            "DefaultTrue" => check_num_of_parameters(0, 0).map(|_| ConditionName::DefaultTrue),
+            "HasEnemyNextToMe" => {
+                check_num_of_parameters(0, 0).map(|_| ConditionName::HasEnemyNextToMe)
+            }
+            "WouldSelfDestructWorthIt" => {
+                check_num_of_parameters(0, 1)?;
+                let max_health_destroyed = match parameters.first() {
+                    Some(health) => get_number(health)?,
+                    None => SELF_DESTRUCTION_DAMAGE,
+                };
+                Ok(ConditionName::WouldSelfDestructWorthIt(
+                    max_health_destroyed,
+                ))
+            }
+            "EnemiesAdjacent" => {
+                check_num_of_parameters(1, 1)?;
+                Ok(ConditionName::EnemiesAdjacent(get_comparison(
+                    &parameters[0],
+                )?))
+            }
+            "FriendsAdjacent" => {
+                check_num_of_parameters(1, 1)?;
+                Ok(ConditionName::FriendsAdjacent(get_comparison(
+                    &parameters[0],
+                )?))
+            }
+            "EnemiesWithin" => {
+                check_num_of_parameters(2, 2)?;
+                Ok(ConditionName::EnemiesWithin(
+                    get_radius(&parameters[0])?,
+                    get_comparison(&parameters[1])?,
+                ))
+            }
+            "FriendsWithin" => {
+                check_num_of_parameters(2, 2)?;
+                Ok(ConditionName::FriendsWithin(
+                    get_radius(&parameters[0])?,
+                    get_comparison(&parameters[1])?,
+                ))
+            }
+            "MyHealthBelow" => {
+                check_num_of_parameters(1, 1)?;
+                Ok(ConditionName::MyHealthBelow(get_number(&parameters[0])?))
+            }
+            _ => Err(format!("unknown condition {}", name)),
+        };
     }
 }
 
-struct AmIAlone {}
+trait Condition {
+    fn is_true(&self, local_area: &LocalArea) -> bool;
+}
+
+type ConditionDictionary = HashMap<ConditionName, Box<dyn Condition>>;
+
+struct DefaultTrue {}
 
-impl Condition for AmIAlone {
+impl Condition for DefaultTrue {
+    fn is_true(&self, _: &LocalArea) -> bool {
+        return true;
+    }
+}
+
+struct RobotsNextToMe {
+    is_friendly: bool,
+    comparison: Comparison,
+}
+
+impl Condition for RobotsNextToMe {
     fn is_true(&self, local_area: &LocalArea) -> bool {
-        let friendly: Vec<&Robot> = local_area
-            .robots
-            .iter()
-            .filter(|v| v.is_friendly == true)
-            .collect();
-        return friendly.len() == 1;
+        let num_of_robots_next_to_me = local_area.get_num_of_robots_next_to_me(self.is_friendly);
+        return self.comparison.is_true(num_of_robots_next_to_me);
     }
 }
 
-struct HasEnemyNextToMe {}
+struct RobotsAroundMe {
+    is_friendly: bool,
+    radius: i8,
+    comparison: Comparison,
+}
 
-impl Condition for HasEnemyNextToMe {
+impl Condition for RobotsAroundMe {
     fn is_true(&self, local_area: &LocalArea) -> bool {
-        let robot_coordinates = Coordinates::new(2, 2);
-        for direction in Direction::iterator() {
-            let vector = direction.to_vector();
-            let neighbouring_cell = robot_coordinates.add_vector(&vector);
-            if local_area.is_enemy_at_coords(&neighbouring_cell) {
-                return true;
-            }
-        }
-        return false;
+        let num_of_robots_around_me =
+            local_area.get_num_of_robots_around_me(self.is_friendly, self.radius);
+        return self.comparison.is_true(num_of_robots_around_me);
     }
 }
 
+struct MyHealthBelow {
+    health: i8,
+}
+
+impl Condition for MyHealthBelow {
+    fn is_true(&self, local_area: &LocalArea) -> bool {
+        return local_area.get_my_health() < self.health;
+    }
+}
+
+struct WouldSelfDestructWorthIt {
+    max_health_destroyed: i8,
+}
+
+impl Condition for WouldSelfDestructWorthIt {
+    fn is_true(&self, local_area: &LocalArea) -> bool {
+        return local_area.get_self_destruction_balance(self.max_health_destroyed) > 0;
+    }
+}
+
+// the conditions without parameters are shorthands for the parameterised ones
+fn create_condition(condition_name: &ConditionName) -> Box<dyn Condition> {
+    return match *condition_name {
+        ConditionName::AmIAlone => Box::new(RobotsAroundMe {
+            is_friendly: true,
+            radius: MAX_AREA_RADIUS,
+            comparison: Comparison::Exactly(0),
+        }),
+        ConditionName::AmIInDanger => Box::new(RobotsNextToMe {
+            is_friendly: false,
+            comparison: Comparison::AtLeast(2),
+        }),
+        ConditionName::DefaultTrue => Box::new(DefaultTrue {}),
+        ConditionName::HasEnemyNextToMe => Box::new(RobotsNextToMe {
+            is_friendly: false,
+            comparison: Comparison::AtLeast(1),
+        }),
+        ConditionName::WouldSelfDestructWorthIt(max_health_destroyed) => {
+            Box::new(WouldSelfDestructWorthIt {
+                max_health_destroyed,
+            })
+        }
+        ConditionName::EnemiesAdjacent(comparison) => Box::new(RobotsNextToMe {
+            is_friendly: false,
+            comparison,
+        }),
+        ConditionName::FriendsAdjacent(comparison) => Box::new(RobotsNextToMe {
+            is_friendly: true,
+            comparison,
+        }),
+        ConditionName::EnemiesWithin(radius, comparison) => Box::new(RobotsAroundMe {
+            is_friendly: false,
+            radius,
+            comparison,
+        }),
+        ConditionName::FriendsWithin(radius, comparison) => Box::new(RobotsAroundMe {
+            is_friendly: true,
+            radius,
+            comparison,
+        }),
+        ConditionName::MyHealthBelow(health) => Box::new(MyHealthBelow { health }),
+    };
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 enum ActionName {
     AttackEnemy,
@@ -291,6 +517,19 @@ enum ActionName {
     SelfDestruct,
 }
 
+impl ActionName {
+    fn from_str(value: &str) -> Option<ActionName> {
+        return match value {
+            "AttackEnemy" => Some(ActionName::AttackEnemy),
+            "SeekFriends" => Some(ActionName::SeekFriends),
+            "GuardMySelf" => Some(ActionName::GuardMySelf),
+            "Escape" => Some(ActionName::Escape),
+            "SelfDestruct" => Some(ActionName::SelfDestruct),
+            _ => None,
+        };
+    }
+}
+
 trait Action {
     fn get_command(&self, local_area: &LocalArea) -> String;
 }
@@ -326,7 +565,7 @@ impl Action for SeekFriends {
             }
             if num_of_enemies < num_of_enemies_at_chosen_direction {
                 num_of_enemies_at_chosen_direction = num_of_enemies;
-                chosen_direction = Some(direction.clone());
+                chosen_direction = Some(*direction);
             }
         }
         if chosen_direction.is_none() {
@@ -354,6 +593,8 @@ impl Action for Escape {
     }
 }
 
+// guards when no enemy is next to the robot, which only happens when a rule picks the action
+// without checking for one
 struct AttackEnemy {}
 
 impl Action for AttackEnemy {
@@ -366,7 +607,7 @@ impl Action for AttackEnemy {
                 return Command::ATTACK.to_str().to_string() + " " + direction.to_str();
             }
         }
-        panic!("Could not find target for AttackEnemy");
+        return Command::GUARD.to_str().to_string();
     }
 }
 
@@ -386,28 +627,236 @@ impl Action for SelfDestruct {
     }
 }
 
+#[derive(Debug, PartialEq)]
+enum ConditionExpression {
+    Is(ConditionName),
+    Not(Box<ConditionExpression>),
+    AnyOf(Vec<ConditionExpression>),
+    AllOf(Vec<ConditionExpression>),
+}
+
+impl ConditionExpression {
+    fn is_true(&self, local_area: &LocalArea, condition_dictionary: &ConditionDictionary) -> bool {
+        return match self {
+            ConditionExpression::Is(condition_name) => condition_dictionary
+                .get(condition_name)
+                .unwrap()
+                .is_true(local_area),
+            ConditionExpression::Not(expression) => {
+                !expression.is_true(local_area, condition_dictionary)
+            }
+            ConditionExpression::AnyOf(expressions) => expressions
+                .iter()
+                .any(|v| v.is_true(local_area, condition_dictionary)),
+            ConditionExpression::AllOf(expressions) => expressions
+                .iter()
+                .all(|v| v.is_true(local_area, condition_dictionary)),
+        };
+    }
+
+    fn get_condition_names(&self) -> Vec<&ConditionName> {
+        return match self {
+            ConditionExpression::Is(condition_name) => vec![condition_name],
+            ConditionExpression::Not(expression) => expression.get_condition_names(),
+            ConditionExpression::AnyOf(expressions) | ConditionExpression::AllOf(expressions) => {
+                expressions
+                    .iter()
+                    .flat_map(|v| v.get_condition_names())
+                    .collect()
+            }
+        };
+    }
+
+    // only DefaultTrue is known to hold whatever the robot sees, the rest depends on the area
+    fn is_always_true(&self) -> bool {
+        return match self {
+            ConditionExpression::Is(condition_name) => {
+                *condition_name == ConditionName::DefaultTrue
+            }
+            ConditionExpression::Not(expression) => expression.is_always_false(),
+            ConditionExpression::AnyOf(expressions) => {
+                expressions.iter().any(|v| v.is_always_true())
+            }
+            ConditionExpression::AllOf(expressions) => {
+                expressions.iter().all(|v| v.is_always_true())
+            }
+        };
+    }
+
+    fn is_always_false(&self) -> bool {
+        return match self {
+            ConditionExpression::Is(_) => false,
+            ConditionExpression::Not(expression) => expression.is_always_true(),
+            ConditionExpression::AnyOf(expressions) => {
+                expressions.iter().all(|v| v.is_always_false())
+            }
+            ConditionExpression::AllOf(expressions) => {
+                expressions.iter().any(|v| v.is_always_false())
+            }
+        };
+    }
+}
+
+// reads the conditions of a rule, e.g. "HasEnemyNextToMe, NOT(AmIInDanger)" or
+// "ANY(AmIAlone, ALL(AmIInDanger, NOT(WouldSelfDestructWorthIt)))"
+struct ConditionParser {
+    tokens: Vec<String>,
+    index: usize,
+}
+
+impl ConditionParser {
+    fn new(text: &str) -> ConditionParser {
+        let mut tokens: Vec<String> = vec![];
+        let mut token = String::new();
+        for character in text.chars() {
+            let is_separator = character == '(' || character == ')' || character == ',';
+            if !is_separator && !character.is_whitespace() {
+                token.push(character);
+                continue;
+            }
+            if !token.is_empty() {
+                tokens.push(token.clone());
+                token.clear();
+            }
+            if is_separator {
+                tokens.push(character.to_string());
+            }
+        }
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+        return ConditionParser { tokens, index: 0 };
+    }
+
+    fn next_token(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        return token;
+    }
+
+    fn expect_token(&mut self, expected_token: &str) -> Result<(), String> {
+        return match self.next_token() {
+            Some(token) if token == expected_token => Ok(()),
+            Some(token) => Err(format!("expected {} instead of {}", expected_token, token)),
+            None => Err(format!("missing {}", expected_token)),
+        };
+    }
+
+    fn parse_expression(&mut self) -> Result<ConditionExpression, String> {
+        let token = self.next_token().ok_or("missing condition")?;
+        return match token.as_str() {
+            "NOT" => {
+                self.expect_token("(")?;
+                let expression = self.parse_expression()?;
+                self.expect_token(")")?;
+                Ok(ConditionExpression::Not(Box::new(expression)))
+            }
+            "ANY" | "ALL" => {
+                self.expect_token("(")?;
+                let expressions = self.parse_expression_list(Some(")"))?;
+                if expressions.is_empty() {
+                    return Err(format!("{} expects at least one condition", token));
+                }
+                if token == "ANY" {
+                    Ok(ConditionExpression::AnyOf(expressions))
+                } else {
+                    Ok(ConditionExpression::AllOf(expressions))
+                }
+            }
+            _ => {
+                let mut parameters: Vec<String> = vec![];
+                if self.tokens.get(self.index).map(|v| v.as_str()) == Some("(") {
+                    self.index += 1;
+                    parameters = self.parse_parameters()?;
+                }
+                ConditionName::from_str(&token, &parameters).map(ConditionExpression::Is)
+            }
+        };
+    }
+
+    // the text of each comma separated parameter up to the closing bracket, ">= 3" reads as ">=3"
+    fn parse_parameters(&mut self) -> Result<Vec<String>, String> {
+        let mut parameters: Vec<String> = vec![];
+        let mut parameter = String::new();
+        loop {
+            match self.next_token() {
+                Some(token) if token == "," || token == ")" => {
+                    if parameter.is_empty() {
+                        return Err("missing parameter".to_string());
+                    }
+                    parameters.push(parameter.clone());
+                    parameter.clear();
+                    if token == ")" {
+                        return Ok(parameters);
+                    }
+                }
+                Some(token) if token == "(" => return Err("unexpected (".to_string()),
+                Some(token) => parameter += &token,
+                None => return Err("missing )".to_string()),
+            }
+        }
+    }
+
+    // comma separated expressions up to the closing bracket, or up to the end of the text for None
+    fn parse_expression_list(
+        &mut self,
+        closing_token: Option<&str>,
+    ) -> Result<Vec<ConditionExpression>, String> {
+        let mut expressions: Vec<ConditionExpression> = vec![];
+        if self.tokens.get(self.index).map(|v| v.as_str()) == closing_token {
+            self.index += 1;
+            return Ok(expressions);
+        }
+        loop {
+            expressions.push(self.parse_expression()?);
+            match self.next_token() {
+                Some(token) if token == "," => continue,
+                token if token.as_deref() == closing_token => return Ok(expressions),
+                Some(token) => return Err(format!("unexpected {}", token)),
+                None => return Err(format!("missing {}", closing_token.unwrap_or(""))),
+            }
+        }
+    }
+}
+
 struct ActionConfiguration {
-    conditions: Vec<ConditionName>,
+    conditions: Vec<ConditionExpression>,
     action_name: ActionName,
 }
 
 impl ActionConfiguration {
-    fn new(action_name: ActionName, conditions: Vec<ConditionName>) -> ActionConfiguration {
+    fn new(action_name: ActionName, conditions: Vec<ConditionExpression>) -> ActionConfiguration {
         return ActionConfiguration {
             action_name,
             conditions,
         };
     }
 
+    // "SelfDestruct: AmIInDanger, WouldSelfDestructWorthIt", the conditions after the colon all have
+    // to hold, a rule without conditions always matches
+    fn from_str(line: &str) -> Result<ActionConfiguration, String> {
+        let (action_name, conditions) = match line.split_once(':') {
+            Some((action_name, conditions)) => (action_name.trim(), conditions),
+            None => (line.trim(), ""),
+        };
+        let action_name = ActionName::from_str(action_name)
+            .ok_or_else(|| format!("unknown action {}", action_name))?;
+        let conditions = ConditionParser::new(conditions).parse_expression_list(None)?;
+        return Ok(ActionConfiguration::new(action_name, conditions));
+    }
+
+    fn is_matching_always(&self) -> bool {
+        return self.conditions.iter().all(|v| v.is_always_true());
+    }
+
     fn is_matching_preconditions(
         &self,
         local_area: &LocalArea,
         condition_dictionary: &ConditionDictionary,
     ) -> bool {
         for precondition in self.conditions.iter() {
-            let precondition_trait = condition_dictionary.get(&precondition).unwrap();
-            let is_true = precondition_trait.is_true(&local_area);
-            if is_true == false {
+            let is_true = precondition.is_true(local_area, condition_dictionary);
+            if !is_true {
                 return false;
             }
         }
@@ -415,46 +864,463 @@ impl ActionConfiguration {
     }
 }
 
-struct GameAI<'a> {
+fn get_default_action_configurations() -> Vec<ActionConfiguration> {
+    return vec![
+        ActionConfiguration::new(
+            ActionName::SelfDestruct,
+            vec![
+                ConditionExpression::Is(ConditionName::AmIInDanger),
+                ConditionExpression::Is(ConditionName::WouldSelfDestructWorthIt(
+                    SELF_DESTRUCTION_DAMAGE,
+                )),
+            ],
+        ),
+        ActionConfiguration::new(
+            ActionName::Escape,
+            vec![ConditionExpression::Is(ConditionName::AmIInDanger)],
+        ),
+        ActionConfiguration::new(
+            ActionName::AttackEnemy,
+            vec![ConditionExpression::Is(ConditionName::HasEnemyNextToMe)],
+        ),
+        ActionConfiguration::new(
+            ActionName::SeekFriends,
+            vec![ConditionExpression::Is(ConditionName::AmIAlone)],
+        ),
+        ActionConfiguration::new(
+            ActionName::GuardMySelf,
+            vec![ConditionExpression::Is(ConditionName::DefaultTrue)],
+        ),
+    ];
+}
+
+// one rule per line in order of priority, blank lines and lines starting with # are skipped
+fn parse_action_configurations(text: &str) -> Result<Vec<ActionConfiguration>, String> {
+    let mut action_configurations: Vec<ActionConfiguration> = vec![];
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let action_configuration = ActionConfiguration::from_str(line)
+            .map_err(|error| format!("line {}: {}", line_index + 1, error))?;
+        action_configurations.push(action_configuration);
+    }
+    return Ok(action_configurations);
+}
+
+// every robot has to end up with an action, so the last rule has to match whatever the robot sees
+fn validate_action_configurations(
+    action_configurations: &[ActionConfiguration],
+    action_dictionary: &ActionDictionary,
+) -> Result<(), String> {
+    for action_configuration in action_configurations.iter() {
+        if !action_dictionary.contains_key(&action_configuration.action_name) {
+            return Err(format!(
+                "action {:?} is not implemented",
+                action_configuration.action_name
+            ));
+        }
+    }
+    return match action_configurations.last() {
+        Some(action_configuration) if action_configuration.is_matching_always() => Ok(()),
+        Some(_) => {
+            Err("the last rule has to match always, e.g. GuardMySelf: DefaultTrue".to_string())
+        }
+        None => Err("no rules".to_string()),
+    };
+}
+
+// one condition for every distinct name and parameters the rules use
+fn create_condition_dictionary(
+    action_configurations: &[ActionConfiguration],
+) -> ConditionDictionary {
+    let mut condition_dictionary: ConditionDictionary = HashMap::new();
+    for condition_name in action_configurations
+        .iter()
+        .flat_map(|v| v.conditions.iter())
+        .flat_map(|v| v.get_condition_names())
+    {
+        if !condition_dictionary.contains_key(condition_name) {
+            condition_dictionary.insert(condition_name.clone(), create_condition(condition_name));
+        }
+    }
+    return condition_dictionary;
+}
+
+// the path after the argument and the text of that file, none when the argument is not given
+fn read_arg_file<'a>(
+    args: &'a [String],
+    arg_name: &str,
+) -> Result<Option<(&'a String, String)>, String> {
+    let path = match args.iter().position(|v| v == arg_name) {
+        Some(arg_index) => args
+            .get(arg_index + 1)
+            .ok_or_else(|| format!("{} expects a path", arg_name))?,
+        None => return Ok(None),
+    };
+    let text =
+        fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path, error))?;
+    return Ok(Some((path, text)));
+}
+
+// the rules come from the file after --rules, the built in ones otherwise
+fn load_action_configurations(args: &[String]) -> Result<Vec<ActionConfiguration>, String> {
+    return match read_arg_file(args, "--rules")? {
+        Some((rules_path, text)) => {
+            parse_action_configurations(&text).map_err(|error| format!("{}: {}", rules_path, error))
+        }
+        None => Ok(get_default_action_configurations()),
+    };
+}
+
+// a number describing the local area, the utilities of the actions are built from these
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Feature {
+    EnemiesAdjacent,
+    FriendsAdjacent,
+    EnemiesAround,
+    FriendsAround,
+    MyHealth,
+    SelfDestructionBalance,
+}
+
+impl Feature {
+    fn from_str(value: &str) -> Option<Feature> {
+        return match value {
+            "EnemiesAdjacent" => Some(Feature::EnemiesAdjacent),
+            "FriendsAdjacent" => Some(Feature::FriendsAdjacent),
+            "EnemiesAround" => Some(Feature::EnemiesAround),
+            "FriendsAround" => Some(Feature::FriendsAround),
+            "MyHealth" => Some(Feature::MyHealth),
+            "SelfDestructionBalance" => Some(Feature::SelfDestructionBalance),
+            _ => None,
+        };
+    }
+
+    fn get_value(self, local_area: &LocalArea) -> f32 {
+        let value = match self {
+            Feature::EnemiesAdjacent => local_area.get_num_of_robots_next_to_me(false),
+            Feature::FriendsAdjacent => local_area.get_num_of_robots_next_to_me(true),
+            Feature::EnemiesAround => {
+                local_area.get_num_of_robots_around_me(false, MAX_AREA_RADIUS)
+            }
+            Feature::FriendsAround => local_area.get_num_of_robots_around_me(true, MAX_AREA_RADIUS),
+            Feature::MyHealth => local_area.get_my_health(),
+            Feature::SelfDestructionBalance => {
+                local_area.get_self_destruction_balance(SELF_DESTRUCTION_DAMAGE)
+            }
+        };
+        return value as f32;
+    }
+}
+
+// turns the value of a feature into a score from 0 to 1
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Curve {
+    // 0 at the first value and 1 at the second, linear in between, either one can be the larger
+    Linear(f32, f32),
+    AtLeast(f32),
+    Below(f32),
+    // 0.5 at the midpoint, rising as steep as the second value, falling when it is negative
+    Logistic(f32, f32),
+}
+
+impl Curve {
+    // "Linear(1, 3)", "AtLeast(2)", "Below(1)" or "Logistic(2, 1.5)"
+    fn from_str(value: &str) -> Result<Curve, String> {
+        let (name, parameters) = value
+            .strip_suffix(')')
+            .and_then(|v| v.split_once('('))
+            .ok_or_else(|| format!("expected a curve like Linear(1, 3) instead of {}", value))?;
+        let name = name.trim();
+        let parameters = parameters
+            .split(',')
+            .map(|v| {
+                return v
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|v| v.is_finite())
+                    .ok_or_else(|| format!("{} expects numbers instead of {}", name, v.trim()));
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+        return match (name, parameters.as_slice()) {
+            ("Linear", [zero_value, one_value]) if zero_value == one_value => Err(format!(
+                "Linear expects two different values instead of {} twice",
+                zero_value
+            )),
+            ("Linear", [zero_value, one_value]) => Ok(Curve::Linear(*zero_value, *one_value)),
+            ("AtLeast", [threshold]) => Ok(Curve::AtLeast(*threshold)),
+            ("Below", [threshold]) => Ok(Curve::Below(*threshold)),
+            ("Logistic", [midpoint, steepness]) => Ok(Curve::Logistic(*midpoint, *steepness)),
+            ("Linear" | "AtLeast" | "Below" | "Logistic", _) => Err(format!(
+                "wrong number of parameters for {}: {}",
+                name,
+                parameters.len()
+            )),
+            _ => Err(format!("unknown curve {}", name)),
+        };
+    }
+
+    fn get_score(self, value: f32) -> f32 {
+        return match self {
+            Curve::Linear(zero_value, one_value) => {
+                ((value - zero_value) / (one_value - zero_value)).clamp(0.0, 1.0)
+            }
+            Curve::AtLeast(threshold) => {
+                if value >= threshold {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Curve::Below(threshold) => {
+                if value < threshold {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Curve::Logistic(midpoint, steepness) => {
+                1.0 / (1.0 + (-steepness * (value - midpoint)).exp())
+            }
+        };
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Consideration {
+    feature: Feature,
+    curve: Curve,
+}
+
+impl Consideration {
+    fn new(feature: Feature, curve: Curve) -> Consideration {
+        return Consideration { feature, curve };
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct UtilityConfiguration {
+    action_name: ActionName,
+    weight: f32,
+    considerations: Vec<Consideration>,
+}
+
+impl UtilityConfiguration {
+    fn new(
+        action_name: ActionName,
+        weight: f32,
+        considerations: Vec<Consideration>,
+    ) -> UtilityConfiguration {
+        return UtilityConfiguration {
+            action_name,
+            weight,
+            considerations,
+        };
+    }
+
+    // "Escape 0.9: EnemiesAdjacent Linear(1, 3), MyHealth Linear(11, 3)", the action and its
+    // weight, then each feature with the curve scoring it, an action without considerations is
+    // scored by its weight alone
+    fn from_str(line: &str) -> Result<UtilityConfiguration, String> {
+        let (action, considerations) = match line.split_once(':') {
+            Some((action, considerations)) => (action, considerations.trim()),
+            None => (line, ""),
+        };
+        let mut action_parts = action.split_whitespace();
+        let action_name = action_parts.next().unwrap_or("");
+        let action_name = ActionName::from_str(action_name)
+            .ok_or_else(|| format!("unknown action {}", action_name))?;
+        let weight = match (action_parts.next(), action_parts.next()) {
+            (Some(weight), None) => weight
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("expected a weight of 0 or more instead of {}", weight))?,
+            _ => {
+                return Err(format!(
+                    "expected an action and its weight instead of {}",
+                    action.trim()
+                ))
+            }
+        };
+        let mut considerations_list: Vec<Consideration> = vec![];
+        if !considerations.is_empty() {
+            // the commas inside a curve's brackets separate its parameters, not considerations
+            let mut consideration_texts: Vec<String> = vec![String::new()];
+            let mut depth = 0;
+            for character in considerations.chars() {
+                match character {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    ',' if depth == 0 => {
+                        consideration_texts.push(String::new());
+                        continue;
+                    }
+                    _ => {}
+                }
+                consideration_texts.last_mut().unwrap().push(character);
+            }
+            for consideration_text in consideration_texts.iter() {
+                let (feature, curve) = consideration_text
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| {
+                        format!(
+                            "expected a feature and its curve instead of {}",
+                            consideration_text.trim()
+                        )
+                    })?;
+                let feature = Feature::from_str(feature)
+                    .ok_or_else(|| format!("unknown feature {}", feature))?;
+                let curve = Curve::from_str(curve.trim())?;
+                considerations_list.push(Consideration::new(feature, curve));
+            }
+        }
+        return Ok(UtilityConfiguration::new(
+            action_name,
+            weight,
+            considerations_list,
+        ));
+    }
+
+    // the weight times every consideration's score, so a single 0 rules the action out
+    fn get_utility(&self, local_area: &LocalArea) -> f32 {
+        let mut utility = self.weight;
+        for consideration in self.considerations.iter() {
+            let value = consideration.feature.get_value(local_area);
+            utility *= consideration.curve.get_score(value);
+        }
+        return utility;
+    }
+}
+
+fn get_default_utility_configurations() -> Vec<UtilityConfiguration> {
+    return vec![
+        UtilityConfiguration::new(
+            ActionName::SelfDestruct,
+            1.0,
+            vec![
+                Consideration::new(Feature::EnemiesAdjacent, Curve::AtLeast(2.0)),
+                Consideration::new(Feature::SelfDestructionBalance, Curve::AtLeast(1.0)),
+            ],
+        ),
+        UtilityConfiguration::new(
+            ActionName::Escape,
+            0.9,
+            vec![
+                Consideration::new(Feature::EnemiesAdjacent, Curve::Linear(1.0, 3.0)),
+                Consideration::new(Feature::MyHealth, Curve::Linear(11.0, 3.0)),
+                Consideration::new(Feature::FriendsAdjacent, Curve::Below(1.0)),
+            ],
+        ),
+        UtilityConfiguration::new(
+            ActionName::AttackEnemy,
+            0.6,
+            vec![
+                Consideration::new(Feature::EnemiesAdjacent, Curve::AtLeast(1.0)),
+                Consideration::new(Feature::MyHealth, Curve::Logistic(2.0, 1.5)),
+            ],
+        ),
+        UtilityConfiguration::new(
+            ActionName::SeekFriends,
+            0.4,
+            vec![
+                Consideration::new(Feature::FriendsAround, Curve::Below(1.0)),
+                Consideration::new(Feature::EnemiesAdjacent, Curve::Below(1.0)),
+                Consideration::new(Feature::EnemiesAround, Curve::Linear(-4.0, 0.0)),
+            ],
+        ),
+        UtilityConfiguration::new(ActionName::GuardMySelf, 0.1, vec![]),
+    ];
+}
+
+fn parse_utility_configurations(text: &str) -> Result<Vec<UtilityConfiguration>, String> {
+    let mut utility_configurations: Vec<UtilityConfiguration> = vec![];
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let utility_configuration = UtilityConfiguration::from_str(line)
+            .map_err(|error| format!("line {}: {}", line_index + 1, error))?;
+        utility_configurations.push(utility_configuration);
+    }
+    if utility_configurations.is_empty() {
+        return Err("no utilities".to_string());
+    }
+    return Ok(utility_configurations);
+}
+
+// the utilities come from the file after --utility, the built in ones otherwise
+fn load_utility_configurations(args: &[String]) -> Result<Vec<UtilityConfiguration>, String> {
+    return match read_arg_file(args, "--utility")? {
+        Some((utility_path, text)) => parse_utility_configurations(&text)
+            .map_err(|error| format!("{}: {}", utility_path, error)),
+        None => Ok(get_default_utility_configurations()),
+    };
+}
+
+trait DecisionMaker {
+    fn pick_action(&self, local_area: &LocalArea) -> ActionName;
+}
+
+// the first rule whose conditions all hold wins
+struct RuleList<'a> {
     condition_dictionary: &'a ConditionDictionary,
     action_configurations: &'a Vec<ActionConfiguration>,
-    action_dictionary: &'a ActionDictionary,
 }
 
-impl GameAI<'_> {
+impl DecisionMaker for RuleList<'_> {
     fn pick_action(&self, local_area: &LocalArea) -> ActionName {
         for action_configuration in self.action_configurations.iter() {
             let is_matching = action_configuration
-                .is_matching_preconditions(&local_area, self.condition_dictionary);
+                .is_matching_preconditions(local_area, self.condition_dictionary);
             if is_matching {
                 return action_configuration.action_name;
             }
         }
         panic!("Could not find action matching conditions")
     }
+}
+
+// the action with the highest utility wins, the one listed first on a tie, and the robot guards
+// when every action has been ruled out
+struct UtilityScoring<'a> {
+    utility_configurations: &'a Vec<UtilityConfiguration>,
+}
+
+impl DecisionMaker for UtilityScoring<'_> {
+    fn pick_action(&self, local_area: &LocalArea) -> ActionName {
+        let mut chosen_action_name = ActionName::GuardMySelf;
+        let mut chosen_utility: f32 = 0.0;
+        for utility_configuration in self.utility_configurations.iter() {
+            let utility = utility_configuration.get_utility(local_area);
+            if utility > chosen_utility {
+                chosen_utility = utility;
+                chosen_action_name = utility_configuration.action_name;
+            }
+        }
+        return chosen_action_name;
+    }
+}
 
+struct GameAI<'a> {
+    decision_maker: &'a dyn DecisionMaker,
+    action_dictionary: &'a ActionDictionary,
+}
+
+impl GameAI<'_> {
     fn get_command_for_robot(&self, local_area: &LocalArea) -> String {
-        let action_name = self.pick_action(&local_area);
+        let action_name = self.decision_maker.pick_action(local_area);
         let action = self.action_dictionary.get(&action_name).unwrap();
-        return action.get_command(&local_area);
+        return action.get_command(local_area);
     }
 }
 
-fn main() {
-    let mut condition_dictionary: ConditionDictionary = HashMap::new();
-
-    condition_dictionary.insert(
-        ConditionName::HasEnemyNextToMe,
-        Box::new(HasEnemyNextToMe {}),
-    );
-    condition_dictionary.insert(ConditionName::AmIAlone, Box::new(AmIAlone {}));
-    condition_dictionary.insert(ConditionName::DefaultTrue, Box::new(DefaultTrue {}));
-    condition_dictionary.insert(ConditionName::AmIInDanger, Box::new(AmIInDanger {}));
-    condition_dictionary.insert(
-        ConditionName::WouldSelfDestructWorthIt,
-        Box::new(WouldSelfDestructWorthIt {}),
-    );
-
+fn create_action_dictionary() -> ActionDictionary {
     let mut action_dictionary: ActionDictionary = HashMap::new();
 
     action_dictionary.insert(ActionName::AttackEnemy, Box::new(AttackEnemy {}));
@@ -462,29 +1328,70 @@ fn main() {
     action_dictionary.insert(ActionName::GuardMySelf, Box::new(GuardMySelf {}));
     action_dictionary.insert(ActionName::Escape, Box::new(Escape {}));
     action_dictionary.insert(ActionName::SelfDestruct, Box::new(SelfDestruct {}));
+    return action_dictionary;
+}
 
-    let action_configurations: Vec<ActionConfiguration> = vec![
-        ActionConfiguration::new(
-            ActionName::SelfDestruct,
-            vec![
-                ConditionName::AmIInDanger,
-                ConditionName::WouldSelfDestructWorthIt,
-            ],
-        ),
-        ActionConfiguration::new(ActionName::Escape, vec![ConditionName::AmIInDanger]),
-        ActionConfiguration::new(
-            ActionName::AttackEnemy,
-            vec![ConditionName::HasEnemyNextToMe],
-        ),
-        ActionConfiguration::new(ActionName::SeekFriends, vec![ConditionName::AmIAlone]),
-        ActionConfiguration::new(ActionName::GuardMySelf, vec![ConditionName::DefaultTrue]),
-    ];
+// "rules" or "utility" after --ai, the rules when it is not given
+fn get_decision_maker_name(args: &[String]) -> Result<&str, String> {
+    let arg_index = match args.iter().position(|v| v == "--ai") {
+        Some(arg_index) => arg_index,
+        None => return Ok("rules"),
+    };
+    return match args.get(arg_index + 1).map(|v| v.as_str()) {
+        Some(decision_maker_name @ ("rules" | "utility")) => Ok(decision_maker_name),
+        Some(decision_maker_name) => Err(format!(
+            "unknown decision maker {}, expected rules or utility",
+            decision_maker_name
+        )),
+        None => Err("--ai expects rules or utility".to_string()),
+    };
+}
 
-    let game_ai = GameAI {
+fn main() {
+    let action_dictionary = create_action_dictionary();
+
+    let args: Vec<String> = env::args().collect();
+    let action_configurations = match load_action_configurations(&args).and_then(|v| {
+        validate_action_configurations(&v, &action_dictionary)?;
+        return Ok(v);
+    }) {
+        Ok(action_configurations) => action_configurations,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let condition_dictionary = create_condition_dictionary(&action_configurations);
+    let (utility_configurations, decision_maker_name) = match load_utility_configurations(&args)
+        .and_then(|v| {
+            return Ok((v, get_decision_maker_name(&args)?));
+        }) {
+        Ok(result) => result,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let rule_list = RuleList {
         condition_dictionary: &condition_dictionary,
-        action_dictionary: &action_dictionary,
         action_configurations: &action_configurations,
     };
+    let utility_scoring = UtilityScoring {
+        utility_configurations: &utility_configurations,
+    };
+    // --ai utility picks actions by their utility instead of by the rules
+    let decision_maker: &dyn DecisionMaker = if decision_maker_name == "utility" {
+        &utility_scoring
+    } else {
+        &rule_list
+    };
+
+    let game_ai = GameAI {
+        decision_maker,
+        action_dictionary: &action_dictionary,
+    };
 
     // game loop
     loop {
@@ -497,15 +1404,13 @@ fn main() {
         for i in 0..number_of_robots as usize {
             let mut local_area = LocalArea::new(i);
 
-            for j in 0..5 as usize {
+            for j in 0..5 {
                 let mut inputs = String::new();
                 io::stdin().read_line(&mut inputs).unwrap();
                 let y = j as i8;
-                let mut x = 0 as i8;
-                for k in inputs.split_whitespace() {
+                for (x, k) in inputs.split_whitespace().enumerate() {
                     let value = parse_input!(k, i8);
-                    local_area.set_local_area_cell(&Coordinates::new(x, y), value);
-                    x += 1;
+                    local_area.set_local_area_cell(&Coordinates::new(x as i8, y), value);
                 }
             }
 
@@ -518,9 +1423,417 @@ fn main() {
 
         for i in 0..number_of_robots as usize {
             let local_area = local_area_list.get(i).unwrap();
-            let command = game_ai.get_command_for_robot(&local_area);
+            let command = game_ai.get_command_for_robot(local_area);
 
             println!("{}", command);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // rows from top to bottom, the robot itself is in the middle
+    fn create_local_area(grid: [[i8; 5]; 5]) -> LocalArea {
+        let mut local_area = LocalArea::new(0);
+        for (y, row) in grid.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                local_area.set_local_area_cell(&Coordinates::new(x as i8, y as i8), *value);
+            }
+        }
+        return local_area;
+    }
+
+    fn get_rules_error(text: &str) -> Option<String> {
+        return parse_action_configurations(text)
+            .and_then(|v| validate_action_configurations(&v, &create_action_dictionary()))
+            .err();
+    }
+
+    fn get_args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|v| v.to_string()).collect();
+    }
+
+    #[test]
+    fn default_and_sample_rules_are_valid() {
+        let action_dictionary = create_action_dictionary();
+        let default_action_configurations = get_default_action_configurations();
+        assert!(
+            validate_action_configurations(&default_action_configurations, &action_dictionary)
+                .is_ok()
+        );
+        let sample_action_configurations =
+            load_action_configurations(&get_args(&["bot", "--rules", "rules.txt"])).ok();
+        assert!(sample_action_configurations
+            .map(|v| validate_action_configurations(&v, &action_dictionary).is_ok())
+            .unwrap_or(false));
+    }
+
+    #[test]
+    fn rules_with_an_unknown_action_are_rejected() {
+        assert_eq!(
+            get_rules_error("Fly: AmIAlone\nGuardMySelf: DefaultTrue"),
+            Some("line 1: unknown action Fly".to_string())
+        );
+    }
+
+    #[test]
+    fn rules_whose_last_rule_can_fail_are_rejected() {
+        assert_eq!(
+            get_rules_error("GuardMySelf: DefaultTrue\nAttackEnemy: HasEnemyNextToMe"),
+            Some("the last rule has to match always, e.g. GuardMySelf: DefaultTrue".to_string())
+        );
+        assert_eq!(
+            get_rules_error("# only a comment\n"),
+            Some("no rules".to_string())
+        );
+    }
+
+    #[test]
+    fn rules_path_has_to_be_given_and_readable() {
+        assert_eq!(
+            load_action_configurations(&get_args(&["bot", "--rules"])).err(),
+            Some("--rules expects a path".to_string())
+        );
+        assert!(
+            load_action_configurations(&get_args(&["bot", "--rules", "missing_rules.txt"]))
+                .err()
+                .unwrap_or_default()
+                .starts_with("could not read missing_rules.txt")
+        );
+    }
+
+    #[test]
+    fn attack_without_an_enemy_next_to_the_robot_guards() {
+        let action_configurations = parse_action_configurations("AttackEnemy: DefaultTrue").ok();
+        assert!(
+            action_configurations.is_some_and(|v| validate_action_configurations(
+                &v,
+                &create_action_dictionary()
+            )
+            .is_ok())
+        );
+        let local_area = create_local_area([
+            [0, 0, 0, 0, 0],
+            [0, -10, 0, 0, 0],
+            [0, 0, 10, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(AttackEnemy {}.get_command(&local_area), "GUARD");
+        let local_area = create_local_area([
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 10, -10, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(AttackEnemy {}.get_command(&local_area), "ATTACK RIGHT");
+    }
+
+    fn parse_conditions(text: &str) -> Result<Vec<ConditionExpression>, String> {
+        return ConditionParser::new(text).parse_expression_list(None);
+    }
+
+    #[test]
+    fn nested_conditions_are_parsed_into_a_tree() {
+        assert_eq!(
+            parse_conditions("ANY(AmIAlone, ALL(AmIInDanger, NOT(WouldSelfDestructWorthIt)))"),
+            Ok(vec![ConditionExpression::AnyOf(vec![
+                ConditionExpression::Is(ConditionName::AmIAlone),
+                ConditionExpression::AllOf(vec![
+                    ConditionExpression::Is(ConditionName::AmIInDanger),
+                    ConditionExpression::Not(Box::new(ConditionExpression::Is(
+                        ConditionName::WouldSelfDestructWorthIt(SELF_DESTRUCTION_DAMAGE)
+                    ))),
+                ]),
+            ])])
+        );
+        assert_eq!(parse_conditions(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn malformed_conditions_are_rejected() {
+        assert_eq!(
+            parse_conditions("ANY()"),
+            Err("ANY expects at least one condition".to_string())
+        );
+        assert_eq!(
+            parse_conditions("AmIAlone)"),
+            Err("unexpected )".to_string())
+        );
+        assert_eq!(
+            parse_conditions("ALL(AmIAlone))"),
+            Err("unexpected )".to_string())
+        );
+        assert_eq!(
+            parse_conditions("NOT(AmIAlone"),
+            Err("missing )".to_string())
+        );
+        assert_eq!(
+            parse_conditions("NOT("),
+            Err("missing condition".to_string())
+        );
+    }
+
+    #[test]
+    fn double_negation_of_default_true_always_matches() {
+        let conditions = parse_conditions("NOT(NOT(DefaultTrue))").unwrap();
+        assert!(conditions[0].is_always_true());
+        assert!(!conditions[0].is_always_false());
+        let conditions = parse_conditions("NOT(DefaultTrue)").unwrap();
+        assert!(!conditions[0].is_always_true());
+        assert!(conditions[0].is_always_false());
+        assert_eq!(get_rules_error("GuardMySelf: NOT(NOT(DefaultTrue))"), None);
+        assert_eq!(
+            get_rules_error("GuardMySelf: ANY(AmIAlone, NOT(DefaultTrue))"),
+            Some("the last rule has to match always, e.g. GuardMySelf: DefaultTrue".to_string())
+        );
+    }
+
+    fn get_condition_name(text: &str) -> Result<ConditionName, String> {
+        return match parse_conditions(text)?.pop() {
+            Some(ConditionExpression::Is(condition_name)) => Ok(condition_name),
+            _ => Err(format!("{} is not a single condition", text)),
+        };
+    }
+
+    #[test]
+    fn two_character_comparisons_are_read_before_one_character_ones() {
+        assert_eq!(Comparison::from_str(">=3"), Some(Comparison::AtLeast(3)));
+        assert_eq!(Comparison::from_str("<=1"), Some(Comparison::AtMost(1)));
+        assert_eq!(Comparison::from_str("==0"), Some(Comparison::Exactly(0)));
+        assert_eq!(Comparison::from_str("<2"), Some(Comparison::Below(2)));
+        assert_eq!(Comparison::from_str(">2"), Some(Comparison::Above(2)));
+        assert_eq!(Comparison::from_str(">= 3"), Some(Comparison::AtLeast(3)));
+        assert_eq!(Comparison::from_str("=1"), None);
+        assert_eq!(Comparison::from_str("3"), None);
+        assert_eq!(
+            get_condition_name("EnemiesAdjacent(>= 2)"),
+            Ok(ConditionName::EnemiesAdjacent(Comparison::AtLeast(2)))
+        );
+    }
+
+    #[test]
+    fn radius_has_to_fit_in_the_local_area() {
+        assert_eq!(
+            get_condition_name("EnemiesWithin(1, >=1)"),
+            Ok(ConditionName::EnemiesWithin(1, Comparison::AtLeast(1)))
+        );
+        assert_eq!(
+            get_condition_name("FriendsWithin(2, ==0)"),
+            Ok(ConditionName::FriendsWithin(2, Comparison::Exactly(0)))
+        );
+        for text in ["EnemiesWithin(0, >=1)", "FriendsWithin(3, ==0)"].iter() {
+            assert!(get_condition_name(text)
+                .err()
+                .unwrap_or_default()
+                .ends_with("expects a radius from 1 to 2"));
+        }
+    }
+
+    #[test]
+    fn condition_names_and_parameter_counts_are_checked() {
+        assert_eq!(
+            get_condition_name("AmIAlone(1)"),
+            Err("wrong number of parameters for AmIAlone: 1".to_string())
+        );
+        assert_eq!(
+            get_condition_name("EnemiesWithin(>=1)"),
+            Err("wrong number of parameters for EnemiesWithin: 1".to_string())
+        );
+        assert_eq!(
+            get_condition_name("WouldSelfDestructWorthIt(2)"),
+            Ok(ConditionName::WouldSelfDestructWorthIt(2))
+        );
+        assert_eq!(
+            get_condition_name("AmIHungry"),
+            Err("unknown condition AmIHungry".to_string())
+        );
+    }
+
+    #[test]
+    fn conditions_without_parameters_keep_their_meaning() {
+        let local_areas = [
+            create_local_area([
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0],
+                [0, 0, 10, 0, 0],
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0],
+            ]),
+            create_local_area([
+                [4, 0, 0, 0, 0],
+                [0, 0, -3, 0, 0],
+                [0, -2, 10, 0, 0],
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0],
+            ]),
+            create_local_area([
+                [0, 0, 0, 0, 0],
+                [0, -4, -10, -10, 0],
+                [0, 3, 2, -2, 0],
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 0, -5],
+            ]),
+        ];
+        let am_i_alone = create_condition(&ConditionName::AmIAlone);
+        let no_friends_within_2 =
+            create_condition(&ConditionName::FriendsWithin(2, Comparison::Exactly(0)));
+        let would_self_destruct_worth_it = create_condition(
+            &ConditionName::WouldSelfDestructWorthIt(SELF_DESTRUCTION_DAMAGE),
+        );
+        let results: Vec<(bool, bool)> = local_areas
+            .iter()
+            .map(|v| {
+                (
+                    am_i_alone.is_true(v),
+                    would_self_destruct_worth_it.is_true(v),
+                )
+            })
+            .collect();
+        assert_eq!(results, vec![(true, false), (false, true), (false, false)]);
+        for local_area in local_areas.iter() {
+            assert_eq!(
+                am_i_alone.is_true(local_area),
+                no_friends_within_2.is_true(local_area)
+            );
+            assert_eq!(
+                would_self_destruct_worth_it.is_true(local_area),
+                local_area.get_self_destruction_balance(SELF_DESTRUCTION_DAMAGE) > 0
+            );
+        }
+    }
+
+    #[test]
+    fn default_and_sample_utilities_match() {
+        let sample_utility_configurations =
+            load_utility_configurations(&get_args(&["bot", "--utility", "utility.txt"]));
+        assert_eq!(
+            sample_utility_configurations,
+            Ok(get_default_utility_configurations())
+        );
+        assert_eq!(
+            load_utility_configurations(&get_args(&["bot"])),
+            Ok(get_default_utility_configurations())
+        );
+    }
+
+    #[test]
+    fn malformed_utilities_are_rejected() {
+        for (text, error) in [
+            ("Fly 1", "line 1: unknown action Fly"),
+            (
+                "Escape: EnemiesAdjacent AtLeast(1)",
+                "line 1: expected an action and its weight instead of Escape",
+            ),
+            (
+                "Escape -1",
+                "line 1: expected a weight of 0 or more instead of -1",
+            ),
+            (
+                "Escape 1: EnemiesAdjacent",
+                "line 1: expected a feature and its curve instead of EnemiesAdjacent",
+            ),
+            (
+                "Escape 1: Hunger AtLeast(1)",
+                "line 1: unknown feature Hunger",
+            ),
+            ("Escape 1: MyHealth Step(1)", "line 1: unknown curve Step"),
+            (
+                "Escape 1: MyHealth Linear(3)",
+                "line 1: wrong number of parameters for Linear: 1",
+            ),
+            (
+                "Escape 1: MyHealth Linear(3, 3)",
+                "line 1: Linear expects two different values instead of 3 twice",
+            ),
+            ("# only a comment", "no utilities"),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                parse_utility_configurations(text).err(),
+                Some(error.to_string())
+            );
+        }
+        assert_eq!(
+            load_utility_configurations(&get_args(&["bot", "--utility"])).err(),
+            Some("--utility expects a path".to_string())
+        );
+    }
+
+    #[test]
+    fn decision_maker_has_to_be_named() {
+        assert_eq!(get_decision_maker_name(&get_args(&["bot"])), Ok("rules"));
+        assert_eq!(
+            get_decision_maker_name(&get_args(&["bot", "--ai", "utility"])),
+            Ok("utility")
+        );
+        assert_eq!(
+            get_decision_maker_name(&get_args(&["bot", "--ai"])),
+            Err("--ai expects rules or utility".to_string())
+        );
+        assert_eq!(
+            get_decision_maker_name(&get_args(&["bot", "--ai", "random"])),
+            Err("unknown decision maker random, expected rules or utility".to_string())
+        );
+    }
+
+    #[test]
+    fn curve_scores_stay_between_0_and_1() {
+        let curves = [
+            Curve::Linear(1.0, 3.0),
+            Curve::Linear(11.0, 3.0),
+            Curve::AtLeast(2.0),
+            Curve::Below(1.0),
+            Curve::Logistic(2.0, 1.5),
+            Curve::Logistic(2.0, -1.5),
+            Curve::Logistic(0.0, 100.0),
+        ];
+        for curve in curves.iter() {
+            for value in -100..=100 {
+                let score = curve.get_score(value as f32);
+                assert!(
+                    (0.0..=1.0).contains(&score),
+                    "{:?} scores {} at {}",
+                    curve,
+                    score,
+                    value
+                );
+            }
+        }
+        assert_eq!(Curve::Linear(1.0, 3.0).get_score(2.0), 0.5);
+        assert_eq!(Curve::Linear(11.0, 3.0).get_score(3.0), 1.0);
+        assert_eq!(Curve::Logistic(2.0, 1.5).get_score(2.0), 0.5);
+    }
+
+    #[test]
+    fn utility_tie_goes_to_the_action_listed_first() {
+        let local_area = create_local_area([[0; 5], [0; 5], [0, 0, 10, 0, 0], [0; 5], [0; 5]]);
+        let pick_action = |text: &str| {
+            let utility_configurations = parse_utility_configurations(text).unwrap();
+            return UtilityScoring {
+                utility_configurations: &utility_configurations,
+            }
+            .pick_action(&local_area);
+        };
+        assert_eq!(
+            pick_action("Escape 0.5\nSeekFriends 0.5"),
+            ActionName::Escape
+        );
+        assert_eq!(
+            pick_action("SeekFriends 0.5\nEscape 0.5"),
+            ActionName::SeekFriends
+        );
+        assert_eq!(
+            pick_action("SeekFriends 0.5\nEscape 0.6"),
+            ActionName::Escape
+        );
+        assert_eq!(
+            pick_action("AttackEnemy 1: EnemiesAdjacent AtLeast(1)\nEscape 0"),
+            ActionName::GuardMySelf
+        );
+    }
+}
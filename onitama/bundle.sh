@@ -0,0 +1,23 @@
+#!/bin/sh
+# concatenates the library modules and the binary into a single file for the codingame submission,
+# every ONITAMA_* variable set while bundling is baked in as an engine parameter override
+set -e
+cd "$(dirname "$0")"
+
+overrides=$(env | sort | sed -n 's/^ONITAMA_\([A-Z0-9_]*\)=\(.*\)$/\1 \2/p' | while read -r name value; do
+    printf '("%s", "%s"), ' "$(echo "$name" | tr 'A-Z' 'a-z')" "$value"
+done)
+
+{
+    head -n 1 src/main.rs
+    for module in $(sed -n 's/^pub mod \(.*\);$/\1/p' src/lib.rs); do
+        echo
+        echo "pub mod $module {"
+        sed "s/^pub static BAKED_ENGINE_PARAMETER_OVERRIDES: .*$/pub static BAKED_ENGINE_PARAMETER_OVERRIDES: \&[(\&str, \&str)] = \&[${overrides%, }];/" "src/$module.rs"
+        echo "}"
+    done
+    echo
+    tail -n +2 src/main.rs | sed 's/onitama::/crate::/g'
+} > target/bundle.rs
+
+echo "target/bundle.rs"
@@ -0,0 +1,115 @@
+use crate::game_state::{create_game_state_from_board, RulesVariant};
+use crate::history::{create_game_history, get_position_key, DEFAULT_DRAW_RULE};
+use crate::move_generation::create_pre_calculated_from_catalogue;
+use crate::search::{search_next_move_to_depth, SearchResult, DEFAULT_SEARCH_OPTIONS};
+
+// name, board rows from rank 5 down to rank 1, cards (white, white, black, black, middle),
+// player to move and search depth; changing any of these changes the bench signature
+pub static BENCH_POSITIONS: [(&str, &str, [i32; 5], usize, usize); 12] = [
+    (
+        "opening-1",
+        "bbBbb/-----/-----/-----/wwWww",
+        [1, 9, 5, 11, 6],
+        0,
+        7,
+    ),
+    (
+        "opening-2",
+        "bbBbb/-----/-----/-----/wwWww",
+        [3, 4, 2, 15, 10],
+        1,
+        7,
+    ),
+    (
+        "opening-3",
+        "bbBb-/----b/-----/-w---/w-Www",
+        [5, 13, 8, 6, 12],
+        0,
+        7,
+    ),
+    (
+        "middlegame-1",
+        "b-B--/-b--b/--w--/-w---/w-W-w",
+        [2, 3, 4, 14, 13],
+        1,
+        7,
+    ),
+    (
+        "middlegame-2",
+        "-bB--/b---b/-w-w-/-----/--W-w",
+        [8, 10, 7, 12, 16],
+        0,
+        7,
+    ),
+    (
+        "middlegame-3",
+        "b-B-b/--b--/-w---/---w-/w-W--",
+        [1, 2, 11, 12, 16],
+        1,
+        7,
+    ),
+    (
+        "middlegame-4",
+        "-b-B-/b----/--wb-/-w---/--W-w",
+        [6, 7, 9, 13, 14],
+        0,
+        7,
+    ),
+    (
+        "middlegame-5",
+        "--Bb-/-b---/--w-b/w----/---Ww",
+        [4, 5, 10, 15, 3],
+        1,
+        7,
+    ),
+    (
+        "endgame-1",
+        "--B--/-----/-b---/---w-/--W--",
+        [12, 15, 16, 7, 8],
+        0,
+        9,
+    ),
+    (
+        "endgame-2",
+        "-B---/--b--/-----/--w--/---W-",
+        [1, 3, 6, 9, 11],
+        1,
+        9,
+    ),
+    (
+        "endgame-3",
+        "--B--/-----/-----/-w---/W----",
+        [2, 4, 14, 5, 13],
+        0,
+        9,
+    ),
+    (
+        "endgame-4",
+        "B-b--/-----/---w-/-----/-W--w",
+        [7, 8, 10, 12, 16],
+        1,
+        8,
+    ),
+];
+
+pub fn search_bench_position(
+    board: &str,
+    card_ids: &[i32; 5],
+    player_id: usize,
+    target_depth: usize,
+) -> SearchResult {
+    let pre_calculated = create_pre_calculated_from_catalogue(card_ids);
+    let game_state = create_game_state_from_board(board, card_ids, player_id, RulesVariant::Base);
+    let mut game_history = create_game_history(DEFAULT_DRAW_RULE);
+    game_history
+        .position_keys
+        .push(get_position_key(&game_state, player_id));
+    return search_next_move_to_depth(
+        &game_state,
+        player_id,
+        &pre_calculated,
+        &mut game_history,
+        DEFAULT_SEARCH_OPTIONS,
+        target_depth,
+    );
+}
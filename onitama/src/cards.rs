@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::game_state::coordinates_to_bitwise_shift;
+
+pub type CardMoves = [i32; 4];
+
+pub type CardCoordinates = [[i32; 2]; 4];
+
+pub type CardMovesMap = HashMap<i32, CardMoves>;
+
+pub static BASE_CARD_CATALOGUE: [(i32, &str, CardCoordinates); 16] = [
+    (1, "TIGER", [[0, 2], [0, -1], [0, 0], [0, 0]]),
+    (2, "DRAGON", [[-2, 1], [2, 1], [-1, -1], [1, -1]]),
+    (3, "FROG", [[-2, 0], [-1, 1], [1, -1], [0, 0]]),
+    (4, "RABBIT", [[2, 0], [1, 1], [-1, -1], [0, 0]]),
+    (5, "CRAB", [[0, 1], [-2, 0], [2, 0], [0, 0]]),
+    (6, "ELEPHANT", [[-1, 1], [1, 1], [-1, 0], [1, 0]]),
+    (7, "GOOSE", [[-1, 1], [-1, 0], [1, 0], [1, -1]]),
+    (8, "ROOSTER", [[1, 1], [1, 0], [-1, 0], [-1, -1]]),
+    (9, "MONKEY", [[-1, 1], [1, 1], [-1, -1], [1, -1]]),
+    (10, "MANTIS", [[-1, 1], [1, 1], [0, -1], [0, 0]]),
+    (11, "HORSE", [[0, 1], [-1, 0], [0, -1], [0, 0]]),
+    (12, "OX", [[0, 1], [1, 0], [0, -1], [0, 0]]),
+    (13, "CRANE", [[0, 1], [-1, -1], [1, -1], [0, 0]]),
+    (14, "BOAR", [[0, 1], [-1, 0], [1, 0], [0, 0]]),
+    (15, "EEL", [[-1, 1], [-1, -1], [1, 0], [0, 0]]),
+    (16, "COBRA", [[1, 1], [1, -1], [-1, 0], [0, 0]]),
+];
+
+// the sensei's path expansion, ordinary movement cards like the base set, mostly in mirrored pairs
+pub static SENSEIS_PATH_CARD_CATALOGUE: [(i32, &str, CardCoordinates); 16] = [
+    (201, "BEAR", [[-1, 1], [0, 1], [1, -1], [0, 0]]),
+    (202, "DOG", [[-1, 1], [-1, 0], [-1, -1], [0, 0]]),
+    (203, "FOX", [[1, 1], [1, 0], [1, -1], [0, 0]]),
+    (204, "GIRAFFE", [[-2, 1], [2, 1], [0, -1], [0, 0]]),
+    (205, "IGUANA", [[-2, 1], [0, 1], [1, -1], [0, 0]]),
+    (206, "KIRIN", [[-1, 2], [1, 2], [0, -2], [0, 0]]),
+    (207, "MOUSE", [[0, 1], [1, 0], [-1, -1], [0, 0]]),
+    (208, "OTTER", [[-1, 1], [2, 0], [1, -1], [0, 0]]),
+    (209, "PANDA", [[0, 1], [1, 1], [-1, -1], [0, 0]]),
+    (210, "PHOENIX", [[-1, 1], [1, 1], [-2, 0], [2, 0]]),
+    (211, "RAT", [[0, 1], [-1, 0], [1, -1], [0, 0]]),
+    (212, "SABLE", [[1, 1], [-2, 0], [-1, -1], [0, 0]]),
+    (213, "SEA SNAKE", [[0, 1], [2, 0], [-1, -1], [0, 0]]),
+    (214, "TANUKI", [[0, 1], [2, 1], [-1, -1], [0, 0]]),
+    (215, "TURTLE", [[-2, 0], [2, 0], [-1, -1], [1, -1]]),
+    (216, "VIPER", [[0, 1], [-2, 0], [1, -1], [0, 0]]),
+];
+
+// wind cards move one of the player's own pieces and then the wind spirit
+pub static WIND_CARD_CATALOGUE: [(i32, &str, CardCoordinates, CardCoordinates); 8] = [
+    (
+        101,
+        "BAT",
+        [[-1, 1], [1, 1], [0, 0], [0, 0]],
+        [[0, 1], [0, -1], [0, 0], [0, 0]],
+    ),
+    (
+        102,
+        "EAGLE",
+        [[0, 1], [0, 0], [0, 0], [0, 0]],
+        [[-1, 1], [1, 1], [-1, -1], [1, -1]],
+    ),
+    (
+        103,
+        "FALCON",
+        [[-1, 0], [1, 0], [0, 0], [0, 0]],
+        [[0, 2], [0, -2], [0, 0], [0, 0]],
+    ),
+    (
+        104,
+        "HAWK",
+        [[-1, 1], [0, 0], [0, 0], [0, 0]],
+        [[-1, 0], [1, 0], [0, 0], [0, 0]],
+    ),
+    (
+        105,
+        "OWL",
+        [[1, 1], [0, 0], [0, 0], [0, 0]],
+        [[-1, 0], [1, 0], [0, 0], [0, 0]],
+    ),
+    (
+        106,
+        "RAVEN",
+        [[0, 1], [0, -1], [0, 0], [0, 0]],
+        [[-2, 0], [2, 0], [0, 0], [0, 0]],
+    ),
+    (
+        107,
+        "SPARROW",
+        [[-1, -1], [1, -1], [0, 0], [0, 0]],
+        [[0, 1], [-1, 0], [1, 0], [0, 0]],
+    ),
+    (
+        108,
+        "SWALLOW",
+        [[0, 2], [0, 0], [0, 0], [0, 0]],
+        [[0, -1], [-1, 0], [1, 0], [0, 0]],
+    ),
+];
+
+// the sets random games are dealt from, the wind cards come with the way of the wind rules instead
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardPool {
+    Base,
+    SenseisPath,
+}
+
+pub static DEFAULT_CARD_POOLS: [CardPool; 1] = [CardPool::Base];
+
+pub fn get_card_pool_card_ids(card_pool: CardPool) -> Vec<i32> {
+    return match card_pool {
+        CardPool::Base => BASE_CARD_CATALOGUE.iter().map(|v| v.0).collect(),
+        CardPool::SenseisPath => SENSEIS_PATH_CARD_CATALOGUE.iter().map(|v| v.0).collect(),
+    };
+}
+
+// a comma separated list, e.g. "base,sensei"
+pub fn parse_card_pools(value: &str) -> Result<Vec<CardPool>, String> {
+    let mut card_pools: Vec<CardPool> = vec![];
+    for name in value.split(',') {
+        let card_pool = match name {
+            "base" => CardPool::Base,
+            "sensei" => CardPool::SenseisPath,
+            _ => return Err(format!("unknown card pool {}", name)),
+        };
+        if !card_pools.contains(&card_pool) {
+            card_pools.push(card_pool);
+        }
+    }
+    return Ok(card_pools);
+}
+
+fn find_movement_card_coordinates(card_id: i32) -> Option<&'static CardCoordinates> {
+    return BASE_CARD_CATALOGUE
+        .iter()
+        .chain(SENSEIS_PATH_CARD_CATALOGUE.iter())
+        .find(|v| v.0 == card_id)
+        .map(|v| &v.2);
+}
+
+pub fn is_card_in_catalogue(card_id: i32) -> bool {
+    return find_movement_card_coordinates(card_id).is_some()
+        || WIND_CARD_CATALOGUE.iter().any(|v| v.0 == card_id);
+}
+
+pub fn get_card_moves_from_coordinates(coordinates: &CardCoordinates) -> CardMoves {
+    let mut moves: CardMoves = [0; 4];
+    for (move_index, [dx, dy]) in coordinates.iter().enumerate() {
+        moves[move_index] = coordinates_to_bitwise_shift(*dx, *dy);
+    }
+    return moves;
+}
+
+pub fn get_card_coordinates_from_catalogue(
+    card_id: i32,
+) -> Option<(CardCoordinates, Option<CardCoordinates>)> {
+    if let Some(card_coordinates) = find_movement_card_coordinates(card_id) {
+        return Some((*card_coordinates, None));
+    }
+    let (_, _, card_coordinates, wind_spirit_coordinates) =
+        WIND_CARD_CATALOGUE.iter().find(|v| v.0 == card_id)?;
+    return Some((*card_coordinates, Some(*wind_spirit_coordinates)));
+}
+
+pub fn get_card_moves_from_catalogue(card_id: i32) -> (CardMoves, Option<CardMoves>) {
+    if let Some(card_coordinates) = find_movement_card_coordinates(card_id) {
+        return (get_card_moves_from_coordinates(card_coordinates), None);
+    }
+    let (_, _, card_moves, wind_spirit_moves) =
+        WIND_CARD_CATALOGUE.iter().find(|v| v.0 == card_id).unwrap();
+    return (
+        get_card_moves_from_coordinates(card_moves),
+        Some(get_card_moves_from_coordinates(wind_spirit_moves)),
+    );
+}
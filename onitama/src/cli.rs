@@ -0,0 +1,343 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::time::Instant;
+
+use crate::bench::{search_bench_position, BENCH_POSITIONS};
+use crate::cards::{parse_card_pools, CardPool, DEFAULT_CARD_POOLS};
+use crate::dataset::{
+    collect_game_record_rows, collect_self_play_rows, get_dataset_header, get_dataset_line,
+    DatasetFormat,
+};
+use crate::engine_parameters::{
+    create_engine_parameters, get_engine_parameter_overrides_from_env,
+    get_engine_parameters_description, EngineParameters,
+};
+use crate::engine_protocol::{create_stdout_engine_output, run_engine_protocol};
+use crate::evaluation::EvaluationBackend;
+use crate::game_state::{RulesVariant, BLACK_PLAYER_ID, WHITE_PLAYER_ID};
+use crate::history::{DrawRule, DEFAULT_DRAW_RULE};
+use crate::neural_network::{
+    collect_training_samples, create_neural_network, get_neural_network_rust_source,
+    train_neural_network,
+};
+use crate::referee::{create_random, play_referee_game, MoveLogFormat};
+use crate::search::{SearchOptions, DEFAULT_SEARCH_OPTIONS};
+
+// the local tools around the engine, every subcommand takes the arguments after its name and
+// reports bad arguments or unreadable files as an error instead of panicking
+
+static SEARCH_FEATURES: [&str; 6] = ["lmr", "futility", "nn", "cards", "race", "none"];
+
+pub fn get_arg(args: &[String], name: &str) -> Option<String> {
+    for (arg_index, arg) in args.iter().enumerate() {
+        if arg != name {
+            continue;
+        }
+        return args.get(arg_index + 1).cloned();
+    }
+    return None;
+}
+
+pub fn has_arg(args: &[String], name: &str) -> bool {
+    return args.iter().any(|v| v == name);
+}
+
+pub fn get_arg_value(args: &[String], name: &str, default_value: u64) -> Result<u64, String> {
+    return match get_arg(args, name) {
+        Some(value) => value
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| format!("{} expects a number, found '{}'", name, value)),
+        None => Ok(default_value),
+    };
+}
+
+pub fn get_rules_variant_from_args(args: &[String]) -> Result<RulesVariant, String> {
+    return match get_arg(args, "--rules").as_deref() {
+        None | Some("base") => Ok(RulesVariant::Base),
+        Some("wind") => Ok(RulesVariant::WayOfTheWind),
+        Some(value) => Err(format!("--rules expects base or wind, found '{}'", value)),
+    };
+}
+
+// the base set unless --card-pools lists others, e.g. "base,sensei"
+pub fn get_card_pools_from_args(args: &[String]) -> Result<Vec<CardPool>, String> {
+    return match get_arg(args, "--card-pools") {
+        Some(value) => parse_card_pools(&value),
+        None => Ok(DEFAULT_CARD_POOLS.to_vec()),
+    };
+}
+
+// defaults, then the ONITAMA_* environment variables, then every --param name=value in order, the
+// outcome goes to stderr so a local run always shows what it played with
+pub fn load_engine_parameters(args: &[String]) -> EngineParameters {
+    let mut overrides = get_engine_parameter_overrides_from_env(env::vars());
+    for (arg_index, arg) in args.iter().enumerate() {
+        if arg != "--param" {
+            continue;
+        }
+        match args.get(arg_index + 1).and_then(|v| v.split_once('=')) {
+            Some((name, value)) => overrides.push((name.to_string(), value.to_string())),
+            None => eprintln!("--param expects name=value"),
+        }
+    }
+    let (engine_parameters, errors) = create_engine_parameters(&overrides);
+    for error in errors.iter() {
+        eprintln!("{}", error);
+    }
+    eprintln!("{}", get_engine_parameters_description(&engine_parameters));
+    return engine_parameters;
+}
+
+// a comma separated list of the search features to enable, e.g. "lmr,futility", "lmr,nn,cards"
+// or "none", nn switches the evaluation to the baked in neural network, cards adds the card flow
+// terms on top of it and race the end game wizard race
+pub fn get_search_options_from_args(
+    args: &[String],
+    name: &str,
+    engine_parameters: EngineParameters,
+) -> Result<SearchOptions, String> {
+    let value = match get_arg(args, name) {
+        Some(value) => value,
+        None => {
+            return Ok(SearchOptions {
+                engine_parameters,
+                ..DEFAULT_SEARCH_OPTIONS
+            })
+        }
+    };
+    let features: Vec<&str> = value.split(',').collect();
+    if let Some(feature) = features.iter().find(|v| !SEARCH_FEATURES.contains(v)) {
+        return Err(format!(
+            "{} expects a list of {}, found '{}'",
+            name,
+            SEARCH_FEATURES.join(","),
+            feature
+        ));
+    }
+    return Ok(SearchOptions {
+        is_late_move_reduction_enabled: features.contains(&"lmr"),
+        is_futility_pruning_enabled: features.contains(&"futility"),
+        evaluation_backend: if features.contains(&"nn") {
+            EvaluationBackend::NeuralNetwork
+        } else {
+            EvaluationBackend::HandWritten
+        },
+        is_card_flow_evaluation_enabled: features.contains(&"cards"),
+        is_wizard_race_evaluation_enabled: features.contains(&"race"),
+        engine_parameters,
+        contempt: 0,
+    });
+}
+
+pub fn get_draw_rule_from_args(args: &[String]) -> Result<DrawRule, String> {
+    return Ok(DrawRule {
+        max_num_of_repetitions: get_arg_value(
+            args,
+            "--repetitions",
+            DEFAULT_DRAW_RULE.max_num_of_repetitions as u64,
+        )? as usize,
+        max_num_of_turns: get_arg_value(
+            args,
+            "--max-turns",
+            DEFAULT_DRAW_RULE.max_num_of_turns as u64,
+        )? as usize,
+    });
+}
+
+fn write_output(args: &[String], content: &str) -> Result<(), String> {
+    return match get_arg(args, "--output") {
+        Some(output_path) => fs::write(&output_path, content)
+            .map_err(|error| format!("could not write {}: {}", output_path, error)),
+        None => {
+            print!("{}", content);
+            Ok(())
+        }
+    };
+}
+
+pub fn run_bench() {
+    let mut total_num_of_nodes: usize = 0;
+    let start = Instant::now();
+
+    for (name, board, card_ids, player_id, target_depth) in BENCH_POSITIONS.iter() {
+        let position_start = Instant::now();
+        let search_result = search_bench_position(board, card_ids, *player_id, *target_depth);
+        total_num_of_nodes += search_result.num_of_nodes;
+
+        println!(
+            "{:<12} {} s: {}, d: {}, n: {}, {}ms",
+            name,
+            search_result.command,
+            search_result.score,
+            search_result.target_depth,
+            search_result.num_of_nodes,
+            position_start.elapsed().as_millis()
+        );
+    }
+
+    let duration = start.elapsed();
+    let num_of_nodes_per_second =
+        (total_num_of_nodes as f64 / duration.as_secs_f64().max(0.001)) as u64;
+    println!(
+        "nodes: {}, time: {}ms, nps: {}",
+        total_num_of_nodes,
+        duration.as_millis(),
+        num_of_nodes_per_second
+    );
+}
+
+pub fn run_referee(args: &[String]) -> Result<(), String> {
+    let num_of_games = get_arg_value(args, "--games", 1)?;
+    let seed = get_arg_value(args, "--seed", 1)?;
+    let draw_rule = get_draw_rule_from_args(args)?;
+
+    let rules_variant = get_rules_variant_from_args(args)?;
+    let card_pools = get_card_pools_from_args(args)?;
+    let engine_parameters = load_engine_parameters(args);
+    let search_options = [
+        get_search_options_from_args(args, "--white", engine_parameters)?,
+        get_search_options_from_args(args, "--black", engine_parameters)?,
+    ];
+
+    let move_log_format = if has_arg(args, "--stats") {
+        MoveLogFormat::Json
+    } else {
+        MoveLogFormat::Text
+    };
+
+    let mut random = create_random(seed);
+    let mut num_of_wins: [usize; 2] = [0; 2];
+    let mut num_of_draws: usize = 0;
+
+    for game_index in 0..num_of_games {
+        let (winner_id, reason, num_of_turns) = play_referee_game(
+            &mut random,
+            draw_rule,
+            rules_variant,
+            &card_pools,
+            search_options,
+            move_log_format,
+            &mut |_, _, _| {},
+        );
+        match winner_id {
+            Some(winner_id) => num_of_wins[winner_id] += 1,
+            None => num_of_draws += 1,
+        }
+        println!(
+            "game {}: {} after {} turns",
+            game_index + 1,
+            reason,
+            num_of_turns
+        );
+    }
+
+    println!(
+        "white: {}, black: {}, draws: {}",
+        num_of_wins[WHITE_PLAYER_ID], num_of_wins[BLACK_PLAYER_ID], num_of_draws
+    );
+    return Ok(());
+}
+
+// self-play games like the referee plays them, or with --replay a file of game records, one per
+// line in the engine protocol's position notation, every searched position becomes a line
+pub fn run_dataset(args: &[String]) -> Result<(), String> {
+    let dataset_format = match get_arg(args, "--format").as_deref() {
+        None | Some("csv") => DatasetFormat::Csv,
+        Some("jsonl") => DatasetFormat::Jsonl,
+        Some(value) => return Err(format!("--format expects csv or jsonl, found '{}'", value)),
+    };
+    let engine_parameters = load_engine_parameters(args);
+    let mut lines: Vec<String> = get_dataset_header(dataset_format).into_iter().collect();
+
+    match get_arg(args, "--replay") {
+        Some(replay_path) => {
+            let search_options = get_search_options_from_args(args, "--search", engine_parameters)?;
+            let game_records = fs::read_to_string(&replay_path)
+                .map_err(|error| format!("could not read {}: {}", replay_path, error))?;
+            for (line_index, game_record) in game_records.lines().enumerate() {
+                if game_record.trim().is_empty() || game_record.trim().starts_with('#') {
+                    continue;
+                }
+                match collect_game_record_rows(game_record, search_options) {
+                    Ok((dataset_rows, game_result)) => lines.extend(
+                        dataset_rows
+                            .iter()
+                            .map(|v| get_dataset_line(dataset_format, v, game_result)),
+                    ),
+                    Err(error) => eprintln!("game record {}: {}", line_index + 1, error),
+                }
+            }
+        }
+        None => {
+            let num_of_games = get_arg_value(args, "--games", 1)?;
+            let draw_rule = get_draw_rule_from_args(args)?;
+            let rules_variant = get_rules_variant_from_args(args)?;
+            let card_pools = get_card_pools_from_args(args)?;
+            let search_options = [
+                get_search_options_from_args(args, "--white", engine_parameters)?,
+                get_search_options_from_args(args, "--black", engine_parameters)?,
+            ];
+            let mut random = create_random(get_arg_value(args, "--seed", 1)?);
+            for _ in 0..num_of_games {
+                let (dataset_rows, game_result) = collect_self_play_rows(
+                    &mut random,
+                    draw_rule,
+                    rules_variant,
+                    &card_pools,
+                    search_options,
+                );
+                lines.extend(
+                    dataset_rows
+                        .iter()
+                        .map(|v| get_dataset_line(dataset_format, v, game_result)),
+                );
+            }
+        }
+    }
+
+    let dataset = lines.iter().map(|v| format!("{}\n", v)).collect::<String>();
+    return write_output(args, &dataset);
+}
+
+pub fn run_train(args: &[String]) -> Result<(), String> {
+    let num_of_games = get_arg_value(args, "--games", 100)? as usize;
+    let num_of_epochs = get_arg_value(args, "--epochs", 20)? as usize;
+    let seed = get_arg_value(args, "--seed", 1)?;
+    let rules_variant = get_rules_variant_from_args(args)?;
+    let card_pools = get_card_pools_from_args(args)?;
+
+    let mut random = create_random(seed);
+    let mut training_samples =
+        collect_training_samples(&mut random, num_of_games, rules_variant, &card_pools);
+    eprintln!(
+        "{} positions from {} games",
+        training_samples.len(),
+        num_of_games
+    );
+
+    let mut neural_network = create_neural_network(&mut random);
+    let mean_squared_errors = train_neural_network(
+        &mut neural_network,
+        &mut training_samples,
+        num_of_epochs,
+        &mut random,
+    );
+    for (epoch_index, mean_squared_error) in mean_squared_errors.iter().enumerate() {
+        eprintln!("epoch {}: mse {:.4}", epoch_index + 1, mean_squared_error);
+    }
+
+    return write_output(args, &get_neural_network_rust_source(&neural_network));
+}
+
+pub fn run_protocol(args: &[String]) -> Result<(), String> {
+    let search_options =
+        get_search_options_from_args(args, "--search", load_engine_parameters(args))?;
+    run_engine_protocol(
+        io::stdin().lock(),
+        search_options,
+        create_stdout_engine_output(),
+    );
+    return Ok(());
+}
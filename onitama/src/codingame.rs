@@ -0,0 +1,278 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::cli::{
+    get_arg_value, get_rules_variant_from_args, get_search_options_from_args, has_arg,
+    load_engine_parameters,
+};
+use crate::game_state::{get_opponent_id, get_player_card, GameState, WHITE_PLAYER_ID};
+use crate::history::{create_game_history, get_position_key, GameHistory, DEFAULT_DRAW_RULE};
+use crate::move_generation::{
+    apply_player_move, create_pre_calculated, get_move_command, get_possible_player_moves,
+    pre_calculate_card_moves, PreCalculated,
+};
+use crate::opponent_model::{
+    create_opponent_model, get_best_choice_match_rate, get_opponent_contempt,
+    get_ranked_opponent_moves, get_top_choice_match_rate, record_opponent_move,
+    OPPONENT_MODEL_NUM_OF_TOP_CHOICES,
+};
+use crate::parser::{
+    create_game_state_from_turn, create_input_reader, is_every_card_dealt, read_player_id,
+    read_turn, TurnInput,
+};
+use crate::search::{
+    search_next_move_before_deadline, search_next_move_to_depth, SearchOptions, SearchResult,
+    EMERGENCY_SEARCH_MIN_TIME_LEFT_MS, FIRST_TURN_TIME_BUDGET_MS, TURN_TIME_BUDGET_MS,
+    TURN_TIME_SAFETY_MARGIN_MS,
+};
+use crate::stats::get_search_stats_json;
+
+// the referee lists the legal actions, the first one is as good a fallback as any, without them a
+// shallow search on whatever could be read is the next best thing
+pub fn get_fallback_command(
+    turn_input: &TurnInput,
+    game_state: &GameState,
+    root_player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+) -> String {
+    if let Some(action) = turn_input.actions.first() {
+        return action.command.clone();
+    }
+    if !is_every_card_dealt(&turn_input.cards) {
+        return "".to_string();
+    }
+    return search_next_move_to_depth(
+        game_state,
+        root_player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        1,
+    )
+    .command;
+}
+
+// the referee knows best how a move is written, a pass in particular, so whenever it lists the
+// legal actions the command has to be one of them, ideally one playing the same card, having to
+// swap the engine's move for another one means the move generation disagrees with the referee
+pub fn get_legal_command(turn_input: &TurnInput, command: &str) -> String {
+    if turn_input.actions.is_empty() || turn_input.actions.iter().any(|v| v.command == command) {
+        return command.to_string();
+    }
+    let card_id = command.split(' ').next().unwrap_or("");
+    let legal_command = match turn_input
+        .actions
+        .iter()
+        .find(|v| v.card_id.to_string() == card_id)
+    {
+        Some(action) => action.command.clone(),
+        None => turn_input.actions[0].command.clone(),
+    };
+    eprintln!(
+        "MOVE MISMATCH: {:?} is not one of the {} legal actions, playing {:?} instead",
+        command,
+        turn_input.actions.len(),
+        legal_command
+    );
+    return legal_command;
+}
+
+// none when the engine does not generate the command itself, the position after it is unknown then
+pub fn get_game_state_after_command(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    command: &str,
+) -> Option<GameState> {
+    for player_move in get_possible_player_moves(game_state, pre_calculated, player_id).iter() {
+        let (card_id, _) = get_player_card(game_state, player_id, player_move.card_index);
+        if get_move_command(pre_calculated, card_id, player_move) == command {
+            return Some(apply_player_move(game_state, player_id, player_move));
+        }
+    }
+    return None;
+}
+
+// the budget counts from the moment the turn's first line arrived, so parsing and the opponent
+// model eat into it, once less than EMERGENCY_SEARCH_MIN_TIME_LEFT_MS is left only a depth 1
+// search is sure to finish, it still finds a legal move and any win in one
+pub fn search_turn_move(
+    game_state: &GameState,
+    root_player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+    deadline: Instant,
+) -> SearchResult {
+    let time_left = deadline.saturating_duration_since(Instant::now());
+    if time_left < Duration::from_millis(EMERGENCY_SEARCH_MIN_TIME_LEFT_MS) {
+        eprintln!("emergency search with {}ms left", time_left.as_millis());
+        return search_next_move_to_depth(
+            game_state,
+            root_player_id,
+            pre_calculated,
+            game_history,
+            search_options,
+            1,
+        );
+    }
+    return search_next_move_before_deadline(
+        game_state,
+        root_player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        deadline,
+    );
+}
+
+pub fn run_codingame(args: &[String]) -> Result<(), String> {
+    let rules_variant = get_rules_variant_from_args(args)?;
+    let search_options =
+        get_search_options_from_args(args, "--search", load_engine_parameters(args))?;
+    let is_stats_logging_enabled = has_arg(args, "--stats");
+    let is_contempt_enabled = has_arg(args, "--contempt");
+    let turn_time_budget_ms = get_arg_value(args, "--turn-time", TURN_TIME_BUDGET_MS)?;
+    let mut pre_calculated = create_pre_calculated();
+
+    let stdin = io::stdin();
+    let mut input_reader = create_input_reader(stdin.lock());
+    let root_player_id = match read_player_id(&mut input_reader) {
+        Ok(root_player_id) => root_player_id,
+        Err(parse_error) => {
+            eprintln!("{}", parse_error);
+            WHITE_PLAYER_ID
+        }
+    };
+
+    let mut game_history = create_game_history(DEFAULT_DRAW_RULE);
+    let mut num_of_own_turns: usize = 0;
+    let mut opponent_model = create_opponent_model();
+    let mut previous_game_state: Option<GameState> = None;
+
+    // game loop
+    while let Some((turn_input, parse_errors)) = read_turn(&mut input_reader, rules_variant) {
+        let start = turn_input.received_at;
+
+        let (game_state, card_moves_map, wind_spirit_card_moves_map) =
+            create_game_state_from_turn(&turn_input, rules_variant);
+        pre_calculate_card_moves(
+            &mut pre_calculated,
+            &card_moves_map,
+            &wind_spirit_card_moves_map,
+        );
+
+        game_history.num_of_turns_played = num_of_own_turns * 2 + root_player_id;
+        game_history
+            .position_keys
+            .push(get_position_key(&game_state, root_player_id));
+        num_of_own_turns += 1;
+
+        if !parse_errors.is_empty() {
+            for parse_error in parse_errors.iter() {
+                eprintln!("{}", parse_error);
+            }
+            let command = get_fallback_command(
+                &turn_input,
+                &game_state,
+                root_player_id,
+                &pre_calculated,
+                &mut game_history,
+                search_options,
+            );
+            println!("{} fallback", command);
+            previous_game_state = None;
+            continue;
+        }
+
+        // the opponent's last move is read off the position they left us in
+        if let Some(previous_game_state) = previous_game_state {
+            let ranked_moves = get_ranked_opponent_moves(
+                &previous_game_state,
+                root_player_id,
+                &pre_calculated,
+                &game_history,
+                search_options,
+            );
+            if let Some(observed_move) =
+                record_opponent_move(&mut opponent_model, &ranked_moves, &game_state)
+            {
+                eprintln!(
+                    "opponent {} rank {}/{}, best {:.2}, top {} {:.2}",
+                    observed_move.command,
+                    observed_move.rank + 1,
+                    observed_move.num_of_moves,
+                    get_best_choice_match_rate(&opponent_model),
+                    OPPONENT_MODEL_NUM_OF_TOP_CHOICES,
+                    get_top_choice_match_rate(&opponent_model)
+                );
+            }
+        }
+        let contempt = if is_contempt_enabled {
+            get_opponent_contempt(&opponent_model)
+        } else {
+            0
+        };
+
+        let time_budget_ms = if num_of_own_turns == 1 {
+            FIRST_TURN_TIME_BUDGET_MS
+        } else {
+            turn_time_budget_ms
+        };
+        let deadline = start
+            + Duration::from_millis(time_budget_ms.saturating_sub(TURN_TIME_SAFETY_MARGIN_MS));
+        let turn_search_options = SearchOptions {
+            contempt,
+            ..search_options
+        };
+        let search_result = search_turn_move(
+            &game_state,
+            root_player_id,
+            &pre_calculated,
+            &mut game_history,
+            turn_search_options,
+            deadline,
+        );
+
+        let command = get_legal_command(&turn_input, &search_result.command);
+        previous_game_state = if command == search_result.command {
+            Some(search_result.game_state)
+        } else {
+            get_game_state_after_command(&game_state, root_player_id, &pre_calculated, &command)
+        };
+        if let Some(previous_game_state) = previous_game_state {
+            game_history.position_keys.push(get_position_key(
+                &previous_game_state,
+                get_opponent_id(root_player_id),
+            ));
+        }
+
+        if is_stats_logging_enabled {
+            eprintln!(
+                "{}",
+                get_search_stats_json(
+                    &search_result,
+                    game_history.num_of_turns_played,
+                    root_player_id,
+                    start.elapsed()
+                )
+            );
+        } else {
+            eprintln!("{}", search_result.num_of_nodes);
+        }
+
+        let duration = start.elapsed().as_millis();
+
+        println!(
+            "{} s: {}, d: {}, n: {}, {}ms",
+            command,
+            search_result.score,
+            search_result.target_depth,
+            search_result.num_of_nodes,
+            duration
+        );
+    }
+    return Ok(());
+}
@@ -0,0 +1,177 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use crate::cards::CardPool;
+use crate::engine_protocol::{
+    apply_move_token, create_engine_session, get_move_token, handle_position,
+};
+use crate::game_state::{
+    get_board_from_game_state, get_middle_card, get_player_card, get_rules_variant_name,
+    get_winner_id, GameState, RulesVariant, NUM_OF_CARDS_PER_PLAYER, NUM_OF_PLAYERS,
+    WHITE_PLAYER_ID,
+};
+use crate::history::DrawRule;
+use crate::referee::{get_player_name, play_referee_game, MoveLogFormat, Random};
+use crate::search::{search_next_move, SearchOptions, SearchResult};
+use crate::stats::get_json_string;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatasetFormat {
+    Csv,
+    Jsonl,
+}
+
+// one searched position, the cards are listed white's, black's and the middle one and the best move
+// is written as a move token, the way the engine protocol's position command takes them, so a row
+// can be loaded back with "position board <board> turn <turn> cards <cards> rules <rules>"
+#[derive(Debug, Clone)]
+pub struct DatasetRow {
+    pub rules_variant: RulesVariant,
+    pub board: String,
+    pub card_ids: [i32; 5],
+    pub player_id: usize,
+    pub score: i32,
+    pub best_move: String,
+}
+
+pub fn create_dataset_row(
+    game_state: &GameState,
+    player_id: usize,
+    rules_variant: RulesVariant,
+    search_result: &SearchResult,
+) -> DatasetRow {
+    let mut card_ids: [i32; 5] = [0; 5];
+    for player_id in 0..NUM_OF_PLAYERS {
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            card_ids[player_id * NUM_OF_CARDS_PER_PLAYER + card_index] =
+                get_player_card(game_state, player_id, card_index).0;
+        }
+    }
+    card_ids[4] = get_middle_card(game_state).0;
+    return DatasetRow {
+        rules_variant,
+        board: get_board_from_game_state(game_state),
+        card_ids,
+        player_id,
+        score: search_result.score,
+        best_move: get_move_token(&search_result.command),
+    };
+}
+
+// from white's point of view, a draw and a game record that stops early both count as 0
+pub fn get_game_result(winner_id: Option<usize>) -> i32 {
+    return match winner_id {
+        Some(winner_id) if winner_id == WHITE_PLAYER_ID => 1,
+        Some(_) => -1,
+        None => 0,
+    };
+}
+
+pub fn get_dataset_header(dataset_format: DatasetFormat) -> Option<String> {
+    return match dataset_format {
+        DatasetFormat::Csv => Some("rules,board,cards,turn,score,best_move,result".to_string()),
+        DatasetFormat::Jsonl => None,
+    };
+}
+
+// the score is the search's, from the side to move's point of view
+pub fn get_dataset_line(
+    dataset_format: DatasetFormat,
+    dataset_row: &DatasetRow,
+    game_result: i32,
+) -> String {
+    let card_ids: Vec<String> = dataset_row.card_ids.iter().map(|v| v.to_string()).collect();
+    return match dataset_format {
+        DatasetFormat::Csv => format!(
+            "{},{},{},{},{},{},{}",
+            get_rules_variant_name(dataset_row.rules_variant),
+            dataset_row.board,
+            card_ids.join(" "),
+            get_player_name(dataset_row.player_id),
+            dataset_row.score,
+            dataset_row.best_move,
+            game_result
+        ),
+        DatasetFormat::Jsonl => format!(
+            "{{\"rules\":{},\"board\":{},\"cards\":[{}],\"turn\":{},\"score\":{},\"best_move\":{},\"result\":{}}}",
+            get_json_string(get_rules_variant_name(dataset_row.rules_variant)),
+            get_json_string(&dataset_row.board),
+            card_ids.join(","),
+            get_json_string(get_player_name(dataset_row.player_id)),
+            dataset_row.score,
+            get_json_string(&dataset_row.best_move),
+            game_result
+        ),
+    };
+}
+
+// every position the two engines search during one game
+pub fn collect_self_play_rows(
+    random: &mut Random,
+    draw_rule: DrawRule,
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+    search_options: [SearchOptions; 2],
+) -> (Vec<DatasetRow>, i32) {
+    let mut dataset_rows: Vec<DatasetRow> = vec![];
+    let (winner_id, _, _) = play_referee_game(
+        random,
+        draw_rule,
+        rules_variant,
+        card_pools,
+        search_options,
+        MoveLogFormat::Silent,
+        &mut |game_state, player_id, search_result| {
+            if !search_result.command.is_empty() {
+                dataset_rows.push(create_dataset_row(
+                    game_state,
+                    player_id,
+                    rules_variant,
+                    search_result,
+                ));
+            }
+        },
+    );
+    return (dataset_rows, get_game_result(winner_id));
+}
+
+// a game record is what follows "position" in the engine protocol, e.g.
+// "startpos cards 1 2 3 4 5 moves 1:C1C2 3:B5B4", every position before a move is searched
+pub fn collect_game_record_rows(
+    game_record: &str,
+    search_options: SearchOptions,
+) -> Result<(Vec<DatasetRow>, i32), String> {
+    let tokens: Vec<&str> = game_record.split_whitespace().collect();
+    let num_of_position_tokens = tokens
+        .iter()
+        .position(|v| *v == "moves")
+        .unwrap_or(tokens.len());
+    let mut position_tokens = vec!["position"];
+    position_tokens.extend_from_slice(&tokens[..num_of_position_tokens]);
+
+    // only the session's position is used, nothing is written to its output
+    let mut session = create_engine_session(search_options, Arc::new(Mutex::new(io::sink())));
+    handle_position(&mut session, &position_tokens)?;
+
+    let mut dataset_rows: Vec<DatasetRow> = vec![];
+    for move_token in tokens[num_of_position_tokens..].iter().skip(1) {
+        let search_result = search_next_move(
+            &session.game_state,
+            session.player_id_to_move,
+            &session.pre_calculated,
+            &mut session.game_history.clone(),
+            search_options,
+        );
+        dataset_rows.push(create_dataset_row(
+            &session.game_state,
+            session.player_id_to_move,
+            session.rules_variant,
+            &search_result,
+        ));
+        apply_move_token(&mut session, move_token)?;
+    }
+    return Ok((
+        dataset_rows,
+        get_game_result(get_winner_id(&session.game_state)),
+    ));
+}
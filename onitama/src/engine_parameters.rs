@@ -0,0 +1,222 @@
+use std::ops::RangeInclusive;
+
+use crate::evaluation::{
+    END_GAME_MAX_NUM_OF_PIECES, GAME_TARGETS, MIDDLE_CARD_THREAT_POINTS,
+    MID_GAME_MAX_NUM_OF_PIECES, MOBILITY_POINTS_PER_MOVE, PIECE_POINTS, PREFERRED_POSITION_POINTS,
+    THREATENED_STUDENT_POINTS, THREATENED_WIZARD_POINTS, WIND_SPIRIT_POINTS,
+    WIZARD_RACE_LEAD_POINTS, WIZARD_RACE_POINTS_PER_MOVE,
+};
+use crate::search::{
+    ASPIRATION_WINDOW, FUTILITY_MARGIN, FUTILITY_PRUNING_MAX_DEPTH,
+    LATE_MOVE_REDUCTION_MIN_CHILD_INDEX, LATE_MOVE_REDUCTION_MIN_DEPTH,
+    TARGET_DEPTH_MIN_NUM_OF_MOVES,
+};
+
+// every tunable number of the evaluation and the search in one place, the statics they default
+// to stay the single source of truth for the submission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineParameters {
+    pub piece_points: i32,
+    pub preferred_position_points: i32,
+    pub wind_spirit_points: i32,
+    pub mid_game_max_num_of_pieces: i32,
+    pub end_game_max_num_of_pieces: i32,
+    pub game_targets: [[i32; 3]; 2],
+    pub mobility_points_per_move: i32,
+    pub threatened_student_points: i32,
+    pub threatened_wizard_points: i32,
+    pub middle_card_threat_points: i32,
+    pub wizard_race_points_per_move: i32,
+    pub wizard_race_lead_points: i32,
+    pub aspiration_window: i32,
+    pub late_move_reduction_min_depth: i32,
+    pub late_move_reduction_min_child_index: i32,
+    pub futility_pruning_max_depth: i32,
+    pub futility_margin: i32,
+    // the fewest estimated moves in total for searching 3, 4 and 5 plies deep, 6 otherwise
+    pub target_depth_min_num_of_moves: [i32; 3],
+}
+
+pub static DEFAULT_ENGINE_PARAMETERS: EngineParameters = EngineParameters {
+    piece_points: PIECE_POINTS,
+    preferred_position_points: PREFERRED_POSITION_POINTS,
+    wind_spirit_points: WIND_SPIRIT_POINTS,
+    mid_game_max_num_of_pieces: MID_GAME_MAX_NUM_OF_PIECES,
+    end_game_max_num_of_pieces: END_GAME_MAX_NUM_OF_PIECES,
+    game_targets: GAME_TARGETS,
+    mobility_points_per_move: MOBILITY_POINTS_PER_MOVE,
+    threatened_student_points: THREATENED_STUDENT_POINTS,
+    threatened_wizard_points: THREATENED_WIZARD_POINTS,
+    middle_card_threat_points: MIDDLE_CARD_THREAT_POINTS,
+    wizard_race_points_per_move: WIZARD_RACE_POINTS_PER_MOVE,
+    wizard_race_lead_points: WIZARD_RACE_LEAD_POINTS,
+    aspiration_window: ASPIRATION_WINDOW,
+    late_move_reduction_min_depth: LATE_MOVE_REDUCTION_MIN_DEPTH as i32,
+    late_move_reduction_min_child_index: LATE_MOVE_REDUCTION_MIN_CHILD_INDEX as i32,
+    futility_pruning_max_depth: FUTILITY_PRUNING_MAX_DEPTH as i32,
+    futility_margin: FUTILITY_MARGIN,
+    target_depth_min_num_of_moves: TARGET_DEPTH_MIN_NUM_OF_MOVES,
+};
+
+// bundle.sh rewrites this line with the ONITAMA_* variables set while bundling, so a submission
+// plays with the tuned values without any flags
+pub static BAKED_ENGINE_PARAMETER_OVERRIDES: &[(&str, &str)] = &[];
+
+pub static ENGINE_PARAMETER_ENV_PREFIX: &str = "ONITAMA_";
+
+// the values each parameter may take, points stay far below the 100000 a win scores, a board
+// mask covers the 25 cells, a late move reduction takes 2 plies off so it needs at least 2 to
+// take them from, and no depth is worth more than the 64 plies a search goes at most
+pub static ENGINE_PARAMETER_RANGES: [(&str, RangeInclusive<i32>); 25] = [
+    ("piece_points", 0..=1000),
+    ("preferred_position_points", 0..=1000),
+    ("wind_spirit_points", 0..=1000),
+    ("mid_game_max_num_of_pieces", 0..=10),
+    ("end_game_max_num_of_pieces", 0..=10),
+    ("white_early_game_target", 0..=(1 << 25) - 1),
+    ("white_mid_game_target", 0..=(1 << 25) - 1),
+    ("white_end_game_target", 0..=(1 << 25) - 1),
+    ("black_early_game_target", 0..=(1 << 25) - 1),
+    ("black_mid_game_target", 0..=(1 << 25) - 1),
+    ("black_end_game_target", 0..=(1 << 25) - 1),
+    ("mobility_points_per_move", 0..=1000),
+    ("threatened_student_points", 0..=1000),
+    ("threatened_wizard_points", 0..=1000),
+    ("middle_card_threat_points", 0..=1000),
+    ("wizard_race_points_per_move", 0..=1000),
+    ("wizard_race_lead_points", 0..=1000),
+    ("aspiration_window", 1..=1000),
+    ("late_move_reduction_min_depth", 2..=64),
+    ("late_move_reduction_min_child_index", 1..=64),
+    ("futility_pruning_max_depth", 0..=64),
+    ("futility_margin", 0..=1000),
+    ("depth_3_min_num_of_moves", 0..=1000),
+    ("depth_4_min_num_of_moves", 0..=1000),
+    ("depth_5_min_num_of_moves", 0..=1000),
+];
+
+fn get_engine_parameter_mut<'a>(
+    engine_parameters: &'a mut EngineParameters,
+    name: &str,
+) -> Option<&'a mut i32> {
+    return match name {
+        "piece_points" => Some(&mut engine_parameters.piece_points),
+        "preferred_position_points" => Some(&mut engine_parameters.preferred_position_points),
+        "wind_spirit_points" => Some(&mut engine_parameters.wind_spirit_points),
+        "mid_game_max_num_of_pieces" => Some(&mut engine_parameters.mid_game_max_num_of_pieces),
+        "end_game_max_num_of_pieces" => Some(&mut engine_parameters.end_game_max_num_of_pieces),
+        "white_early_game_target" => Some(&mut engine_parameters.game_targets[0][0]),
+        "white_mid_game_target" => Some(&mut engine_parameters.game_targets[0][1]),
+        "white_end_game_target" => Some(&mut engine_parameters.game_targets[0][2]),
+        "black_early_game_target" => Some(&mut engine_parameters.game_targets[1][0]),
+        "black_mid_game_target" => Some(&mut engine_parameters.game_targets[1][1]),
+        "black_end_game_target" => Some(&mut engine_parameters.game_targets[1][2]),
+        "mobility_points_per_move" => Some(&mut engine_parameters.mobility_points_per_move),
+        "threatened_student_points" => Some(&mut engine_parameters.threatened_student_points),
+        "threatened_wizard_points" => Some(&mut engine_parameters.threatened_wizard_points),
+        "middle_card_threat_points" => Some(&mut engine_parameters.middle_card_threat_points),
+        "wizard_race_points_per_move" => Some(&mut engine_parameters.wizard_race_points_per_move),
+        "wizard_race_lead_points" => Some(&mut engine_parameters.wizard_race_lead_points),
+        "aspiration_window" => Some(&mut engine_parameters.aspiration_window),
+        "late_move_reduction_min_depth" => {
+            Some(&mut engine_parameters.late_move_reduction_min_depth)
+        }
+        "late_move_reduction_min_child_index" => {
+            Some(&mut engine_parameters.late_move_reduction_min_child_index)
+        }
+        "futility_pruning_max_depth" => Some(&mut engine_parameters.futility_pruning_max_depth),
+        "futility_margin" => Some(&mut engine_parameters.futility_margin),
+        "depth_3_min_num_of_moves" => Some(&mut engine_parameters.target_depth_min_num_of_moves[0]),
+        "depth_4_min_num_of_moves" => Some(&mut engine_parameters.target_depth_min_num_of_moves[1]),
+        "depth_5_min_num_of_moves" => Some(&mut engine_parameters.target_depth_min_num_of_moves[2]),
+        _ => None,
+    };
+}
+
+pub fn get_engine_parameter(engine_parameters: &EngineParameters, name: &str) -> Option<i32> {
+    let mut engine_parameters = *engine_parameters;
+    return get_engine_parameter_mut(&mut engine_parameters, name).map(|v| *v);
+}
+
+// board masks read best in binary, so "0b00000_01110_01110_01110_00000" is accepted as well
+pub fn parse_engine_parameter_value(value: &str) -> Option<i32> {
+    let value = value.trim().replace('_', "");
+    if let Some(binary_value) = value.strip_prefix("0b") {
+        return i32::from_str_radix(binary_value, 2).ok();
+    }
+    return value.parse::<i32>().ok();
+}
+
+pub fn set_engine_parameter(
+    engine_parameters: &mut EngineParameters,
+    name: &str,
+    value: &str,
+) -> Result<(), String> {
+    let range = match ENGINE_PARAMETER_RANGES.iter().find(|v| v.0 == name) {
+        Some((_, range)) => range,
+        None => return Err(format!("unknown engine parameter {}", name)),
+    };
+    let parsed_value = match parse_engine_parameter_value(value) {
+        Some(parsed_value) => parsed_value,
+        None => return Err(format!("invalid value {} for {}", value, name)),
+    };
+    if !range.contains(&parsed_value) {
+        return Err(format!(
+            "{} for {} is out of range, expected {} to {}",
+            value,
+            name,
+            range.start(),
+            range.end()
+        ));
+    }
+    *get_engine_parameter_mut(engine_parameters, name).unwrap() = parsed_value;
+    return Ok(());
+}
+
+// ONITAMA_FUTILITY_MARGIN=120 overrides futility_margin, anything without the prefix is ignored
+pub fn get_engine_parameter_overrides_from_env(
+    env_vars: impl Iterator<Item = (String, String)>,
+) -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = vec![];
+    for (key, value) in env_vars {
+        if let Some(name) = key.strip_prefix(ENGINE_PARAMETER_ENV_PREFIX) {
+            overrides.push((name.to_lowercase(), value));
+        }
+    }
+    overrides.sort();
+    return overrides;
+}
+
+// the baked in overrides come first, later overrides win, a bad override is reported and skipped
+// so a typo never stops the engine from playing
+pub fn create_engine_parameters(overrides: &[(String, String)]) -> (EngineParameters, Vec<String>) {
+    let mut engine_parameters = DEFAULT_ENGINE_PARAMETERS;
+    let mut errors: Vec<String> = vec![];
+    let baked_overrides = BAKED_ENGINE_PARAMETER_OVERRIDES
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+    for (name, value) in baked_overrides.chain(overrides.iter().cloned()) {
+        if let Err(error) = set_engine_parameter(&mut engine_parameters, &name, &value) {
+            errors.push(error);
+        }
+    }
+    return (engine_parameters, errors);
+}
+
+pub fn get_engine_parameters_description(engine_parameters: &EngineParameters) -> String {
+    let values: Vec<String> = ENGINE_PARAMETER_RANGES
+        .iter()
+        .map(|(name, _)| {
+            let value = get_engine_parameter(engine_parameters, name).unwrap();
+            if name.ends_with("_target") {
+                let rows: Vec<String> = (0..5)
+                    .rev()
+                    .map(|row_index| format!("{:05b}", (value >> (row_index * 5)) & 0b11111))
+                    .collect();
+                return format!("{}=0b{}", name, rows.join("_"));
+            }
+            return format!("{}={}", name, value);
+        })
+        .collect();
+    return format!("params {}", values.join(" "));
+}
@@ -0,0 +1,463 @@
+use std::io;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::cards::{is_card_in_catalogue, BASE_CARD_CATALOGUE};
+use crate::game_state::{
+    check_board_piece_counts, create_game_state_from_board, create_initial_game_state,
+    get_opponent_id, get_player_card, GameState, RulesVariant, BLACK_PLAYER_ID, WHITE_PLAYER_ID,
+};
+use crate::history::{create_game_history, get_position_key, GameHistory, DEFAULT_DRAW_RULE};
+use crate::move_generation::{
+    apply_player_move, create_pre_calculated_from_catalogue, get_move_command, get_perft,
+    get_possible_player_moves, PreCalculated,
+};
+use crate::search::{
+    create_search_limits, get_search_evaluation, get_target_depth_for_game_state,
+    search_next_move_with_limits, search_next_move_with_tree, SearchOptions, MAX_NUM_OF_TREE_NODES,
+    MAX_SEARCH_DEPTH,
+};
+use crate::tree_export::get_search_tree_dot;
+
+// a line based protocol in the spirit of uci, moves are written as the codingame command with
+// the spaces replaced by ':' so that a move list fits on one line, e.g. "5:A1A2" or "101:B1B2:C3C4"
+//
+//   position startpos [turn white|black] [cards c1 c2 c3 c4 c5] [rules base|wind] [moves m1 m2 ..]
+//   position board bbBbb/-----/--S--/-----/wwWww [turn ..] [cards ..] [rules ..] [moves ..]
+//   go [depth n] [movetime ms] [infinite]   every search also ends once its tree holds
+//                                           MAX_NUM_OF_TREE_NODES nodes, infinite included
+//   eval   the score the search gives the position, from the side to move's point of view
+//   stop | perft n | isready | quit
+//   dot [plies k] [depth n]   searches the position and prints the top k plies of the tree as dot,
+//                             the tree is capped at MAX_NUM_OF_TREE_NODES nodes as well
+//
+// cards are listed as white's two, black's two and the middle card
+
+// the search thread writes its lines as well, so the output is shared, stdout unless a test
+// wants to read what the engine answered
+pub type EngineOutput = Arc<Mutex<dyn Write + Send>>;
+
+pub struct EngineSession {
+    pub rules_variant: RulesVariant,
+    pub card_ids: [i32; 5],
+    pub pre_calculated: PreCalculated,
+    pub game_state: GameState,
+    pub player_id_to_move: usize,
+    pub game_history: GameHistory,
+    pub search_options: SearchOptions,
+    pub search_stop_signal: Option<Arc<AtomicBool>>,
+    pub search_thread: Option<JoinHandle<()>>,
+    pub output: EngineOutput,
+}
+
+pub fn create_stdout_engine_output() -> EngineOutput {
+    return Arc::new(Mutex::new(io::stdout()));
+}
+
+fn write_engine_output(output: &EngineOutput, text: &str) {
+    let mut output = output.lock().unwrap();
+    // nobody is left to tell when the output is gone
+    let _ = output.write_all(text.as_bytes());
+    let _ = output.flush();
+}
+
+fn write_engine_line(output: &EngineOutput, line: &str) {
+    write_engine_output(output, &format!("{}\n", line));
+}
+
+pub fn get_move_token(command: &str) -> String {
+    return command.replace(' ', ":");
+}
+
+pub fn create_engine_session(search_options: SearchOptions, output: EngineOutput) -> EngineSession {
+    let mut card_ids: [i32; 5] = [0; 5];
+    for (card_index, card_id) in card_ids.iter_mut().enumerate() {
+        *card_id = BASE_CARD_CATALOGUE[card_index].0;
+    }
+    let game_state = create_initial_game_state(&card_ids, WHITE_PLAYER_ID, RulesVariant::Base);
+    let mut game_history = create_game_history(DEFAULT_DRAW_RULE);
+    game_history
+        .position_keys
+        .push(get_position_key(&game_state, WHITE_PLAYER_ID));
+    return EngineSession {
+        rules_variant: RulesVariant::Base,
+        card_ids,
+        pre_calculated: create_pre_calculated_from_catalogue(&card_ids),
+        game_state,
+        player_id_to_move: WHITE_PLAYER_ID,
+        game_history,
+        search_options,
+        search_stop_signal: None,
+        search_thread: None,
+        output,
+    };
+}
+
+fn get_token_value<'a>(tokens: &[&'a str], name: &str) -> Option<&'a str> {
+    let token_index = tokens.iter().position(|v| *v == name)?;
+    return tokens.get(token_index + 1).copied();
+}
+
+fn parse_player_id(value: &str) -> Result<usize, String> {
+    return match value {
+        "white" | "0" => Ok(WHITE_PLAYER_ID),
+        "black" | "1" => Ok(BLACK_PLAYER_ID),
+        _ => Err(format!("unknown player: {}", value)),
+    };
+}
+
+fn parse_rules_variant(value: &str) -> Result<RulesVariant, String> {
+    return match value {
+        "base" => Ok(RulesVariant::Base),
+        "wind" => Ok(RulesVariant::WayOfTheWind),
+        _ => Err(format!("unknown rules: {}", value)),
+    };
+}
+
+fn parse_card_ids(tokens: &[&str]) -> Result<Option<[i32; 5]>, String> {
+    let token_index = match tokens.iter().position(|v| *v == "cards") {
+        Some(token_index) => token_index,
+        None => return Ok(None),
+    };
+    let mut card_ids: [i32; 5] = [0; 5];
+    for (card_index, card_id) in card_ids.iter_mut().enumerate() {
+        let value = tokens
+            .get(token_index + 1 + card_index)
+            .ok_or_else(|| "cards expects five card ids".to_string())?;
+        *card_id = value
+            .parse::<i32>()
+            .map_err(|_| format!("invalid card id: {}", value))?;
+    }
+    return Ok(Some(card_ids));
+}
+
+pub fn apply_move_token(session: &mut EngineSession, move_token: &str) -> Result<(), String> {
+    let player_id = session.player_id_to_move;
+    let player_moves =
+        get_possible_player_moves(&session.game_state, &session.pre_calculated, player_id);
+    for player_move in player_moves.iter() {
+        let (card_id, _) = get_player_card(&session.game_state, player_id, player_move.card_index);
+        let command = get_move_command(&session.pre_calculated, card_id, player_move);
+        if get_move_token(&command) != move_token {
+            continue;
+        }
+        session.game_state = apply_player_move(&session.game_state, player_id, player_move);
+        session.player_id_to_move = get_opponent_id(player_id);
+        session.game_history.num_of_turns_played += 1;
+        session.game_history.position_keys.push(get_position_key(
+            &session.game_state,
+            session.player_id_to_move,
+        ));
+        return Ok(());
+    }
+    return Err(format!("illegal move: {}", move_token));
+}
+
+// the session is only replaced once the whole command made sense
+pub fn handle_position(session: &mut EngineSession, tokens: &[&str]) -> Result<(), String> {
+    let rules_variant = match get_token_value(tokens, "rules") {
+        Some(value) => parse_rules_variant(value)?,
+        None => RulesVariant::Base,
+    };
+    let player_id_to_move = match get_token_value(tokens, "turn") {
+        Some(value) => parse_player_id(value)?,
+        None => WHITE_PLAYER_ID,
+    };
+    let card_ids = parse_card_ids(tokens)?.unwrap_or(session.card_ids);
+    if card_ids.iter().any(|v| !is_card_in_catalogue(*v)) {
+        return Err(format!("unknown card in {:?}", card_ids));
+    }
+    if (1..card_ids.len()).any(|v| card_ids[..v].contains(&card_ids[v])) {
+        return Err(format!("duplicate card in {:?}", card_ids));
+    }
+
+    let game_state = match tokens.get(1).copied() {
+        Some("startpos") => create_initial_game_state(&card_ids, player_id_to_move, rules_variant),
+        Some("board") => {
+            let board = get_token_value(tokens, "board").unwrap_or("");
+            if board.split('/').count() != 5 || board.split('/').any(|v| v.chars().count() != 5) {
+                return Err(format!("board expects five rows of five cells: {}", board));
+            }
+            check_board_piece_counts(board.split('/'))?;
+            create_game_state_from_board(board, &card_ids, player_id_to_move, rules_variant)
+        }
+        _ => return Err("position expects startpos or board".to_string()),
+    };
+
+    let mut game_history = create_game_history(DEFAULT_DRAW_RULE);
+    game_history
+        .position_keys
+        .push(get_position_key(&game_state, player_id_to_move));
+    let mut next_session = EngineSession {
+        rules_variant,
+        card_ids,
+        pre_calculated: create_pre_calculated_from_catalogue(&card_ids),
+        game_state,
+        player_id_to_move,
+        game_history,
+        search_options: session.search_options,
+        search_stop_signal: None,
+        search_thread: None,
+        output: session.output.clone(),
+    };
+    if let Some(token_index) = tokens.iter().position(|v| *v == "moves") {
+        for move_token in tokens[token_index + 1..].iter() {
+            apply_move_token(&mut next_session, move_token)?;
+        }
+    }
+
+    session.rules_variant = next_session.rules_variant;
+    session.card_ids = next_session.card_ids;
+    session.pre_calculated = next_session.pre_calculated;
+    session.game_state = next_session.game_state;
+    session.player_id_to_move = next_session.player_id_to_move;
+    session.game_history = next_session.game_history;
+    return Ok(());
+}
+
+fn parse_go_value(tokens: &[&str], name: &str) -> Result<Option<u64>, String> {
+    return match get_token_value(tokens, name) {
+        Some(value) => value
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| format!("invalid {}: {}", name, value)),
+        None => Ok(None),
+    };
+}
+
+// commands other than stop and quit let a running search finish, so a script can pipe
+// "go depth 8" followed by the next position without the search being cut short
+pub fn wait_for_search(session: &mut EngineSession) {
+    session.search_stop_signal = None;
+    if let Some(search_thread) = session.search_thread.take() {
+        search_thread.join().unwrap();
+    }
+}
+
+pub fn stop_search(session: &mut EngineSession) {
+    if let Some(search_stop_signal) = session.search_stop_signal.as_ref() {
+        search_stop_signal.store(true, Ordering::Relaxed);
+    }
+    wait_for_search(session);
+}
+
+fn write_node_limit_info(output: &EngineOutput, num_of_nodes: usize) {
+    if num_of_nodes >= MAX_NUM_OF_TREE_NODES {
+        write_engine_line(
+            output,
+            &format!(
+                "info string node limit of {} reached",
+                MAX_NUM_OF_TREE_NODES
+            ),
+        );
+    }
+}
+
+// the search runs on its own thread so that stop can still be read from the input
+fn handle_go(session: &mut EngineSession, tokens: &[&str]) -> Result<(), String> {
+    let depth = parse_go_value(tokens, "depth")?;
+    let movetime = parse_go_value(tokens, "movetime")?;
+    let is_infinite = tokens.contains(&"infinite");
+    wait_for_search(session);
+
+    let mut search_limits = create_search_limits(match depth {
+        Some(depth) => depth as usize,
+        None if movetime.is_some() || is_infinite => MAX_SEARCH_DEPTH,
+        None => get_target_depth_for_game_state(
+            &session.game_state,
+            &session.pre_calculated,
+            &session.search_options.engine_parameters,
+        ),
+    });
+    if let Some(movetime) = movetime {
+        search_limits.deadline = Some(Instant::now() + Duration::from_millis(movetime));
+    }
+    search_limits.max_num_of_nodes = Some(MAX_NUM_OF_TREE_NODES);
+    let search_stop_signal = Arc::new(AtomicBool::new(false));
+    search_limits.stop_signal = Some(search_stop_signal.clone());
+
+    let game_state = session.game_state;
+    let player_id = session.player_id_to_move;
+    let pre_calculated = session.pre_calculated.clone();
+    let mut game_history = session.game_history.clone();
+    let search_options = session.search_options;
+    let output = session.output.clone();
+    session.search_stop_signal = Some(search_stop_signal);
+    session.search_thread = Some(thread::spawn(move || {
+        let start = Instant::now();
+        let search_result = search_next_move_with_limits(
+            &game_state,
+            player_id,
+            &pre_calculated,
+            &mut game_history,
+            search_options,
+            &search_limits,
+            &mut |search_result| {
+                write_engine_line(
+                    &output,
+                    &format!(
+                        "info depth {} score {} nodes {} time {} pv {}",
+                        search_result.target_depth,
+                        search_result.score,
+                        search_result.num_of_nodes,
+                        start.elapsed().as_millis(),
+                        get_move_token(&search_result.command)
+                    ),
+                );
+            },
+        );
+        write_node_limit_info(&output, search_result.num_of_nodes);
+        if search_result.command.is_empty() {
+            write_engine_line(&output, "bestmove none");
+            return;
+        }
+        write_engine_line(
+            &output,
+            &format!("bestmove {}", get_move_token(&search_result.command)),
+        );
+    }));
+    return Ok(());
+}
+
+fn handle_perft(session: &EngineSession, tokens: &[&str]) -> Result<(), String> {
+    let depth = tokens
+        .get(1)
+        .and_then(|v| v.parse::<usize>().ok())
+        .ok_or_else(|| "perft expects a depth".to_string())?;
+    if depth == 0 {
+        write_engine_line(&session.output, "perft 0 1");
+        return Ok(());
+    }
+    let player_id = session.player_id_to_move;
+    let mut num_of_leaf_nodes = 0;
+    for player_move in
+        get_possible_player_moves(&session.game_state, &session.pre_calculated, player_id).iter()
+    {
+        let (card_id, _) = get_player_card(&session.game_state, player_id, player_move.card_index);
+        let command = get_move_command(&session.pre_calculated, card_id, player_move);
+        let next_game_state = apply_player_move(&session.game_state, player_id, player_move);
+        let num_of_leaf_nodes_for_move = get_perft(
+            &next_game_state,
+            &session.pre_calculated,
+            get_opponent_id(player_id),
+            depth - 1,
+        );
+        write_engine_line(
+            &session.output,
+            &format!(
+                "{} {}",
+                get_move_token(&command),
+                num_of_leaf_nodes_for_move
+            ),
+        );
+        num_of_leaf_nodes += num_of_leaf_nodes_for_move;
+    }
+    write_engine_line(
+        &session.output,
+        &format!("perft {} {}", depth, num_of_leaf_nodes),
+    );
+    return Ok(());
+}
+
+fn handle_dot(session: &mut EngineSession, tokens: &[&str]) -> Result<(), String> {
+    let num_of_plies = parse_go_value(tokens, "plies")?.unwrap_or(2) as usize;
+    let depth = match parse_go_value(tokens, "depth")? {
+        Some(depth) => depth as usize,
+        None => get_target_depth_for_game_state(
+            &session.game_state,
+            &session.pre_calculated,
+            &session.search_options.engine_parameters,
+        ),
+    };
+    let mut game_history = session.game_history.clone();
+    let mut search_limits = create_search_limits(depth);
+    search_limits.max_num_of_nodes = Some(MAX_NUM_OF_TREE_NODES);
+    let (search_result, root_node) = search_next_move_with_tree(
+        &session.game_state,
+        session.player_id_to_move,
+        &session.pre_calculated,
+        &mut game_history,
+        session.search_options,
+        &search_limits,
+        &mut |_| {},
+    );
+    write_node_limit_info(&session.output, search_result.num_of_nodes);
+    write_engine_output(
+        &session.output,
+        &get_search_tree_dot(&root_node, num_of_plies),
+    );
+    return Ok(());
+}
+
+// returns false once the engine should quit
+pub fn handle_engine_command(session: &mut EngineSession, line: &str) -> bool {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let result = match tokens.first().copied() {
+        None => Ok(()),
+        Some("position") => {
+            wait_for_search(session);
+            handle_position(session, &tokens)
+        }
+        Some("go") => handle_go(session, &tokens),
+        Some("stop") => {
+            stop_search(session);
+            Ok(())
+        }
+        Some("eval") => {
+            wait_for_search(session);
+            let evaluation = get_search_evaluation(
+                &session.game_state,
+                &session.pre_calculated,
+                &session.search_options,
+                session.player_id_to_move,
+                session.player_id_to_move,
+            );
+            write_engine_line(&session.output, &format!("eval {}", evaluation));
+            Ok(())
+        }
+        Some("perft") => {
+            wait_for_search(session);
+            handle_perft(session, &tokens)
+        }
+        Some("dot") => {
+            wait_for_search(session);
+            handle_dot(session, &tokens)
+        }
+        Some("isready") => {
+            write_engine_line(&session.output, "readyok");
+            Ok(())
+        }
+        Some("quit") => {
+            stop_search(session);
+            return false;
+        }
+        Some(command) => Err(format!("unknown command: {}", command)),
+    };
+    if let Err(error) = result {
+        write_engine_line(&session.output, &format!("info string {}", error));
+    }
+    return true;
+}
+
+pub fn run_engine_protocol(
+    input: impl BufRead,
+    search_options: SearchOptions,
+    output: EngineOutput,
+) {
+    let mut session = create_engine_session(search_options, output);
+    for line in input.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        if !handle_engine_command(&mut session, &line) {
+            return;
+        }
+    }
+    // a search still running when the input closes gets to finish and report its move
+    wait_for_search(&mut session);
+}
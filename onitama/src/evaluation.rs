@@ -0,0 +1,366 @@
+use crate::engine_parameters::{EngineParameters, DEFAULT_ENGINE_PARAMETERS};
+use crate::game_state::{
+    get_middle_card, get_opponent_id, get_player_card, get_player_piece_position,
+    get_player_pieces_bitmask, get_wind_spirit_position, GameState, BLACK_PLAYER_ID,
+    BLACK_PLAYER_SHRINE_MASK, GAME_STATE_WIZARD_OFFSET, NUM_OF_CARDS_PER_PLAYER,
+    NUM_OF_PIECES_PER_PLAYER, NUM_OF_PLAYERS, WHITE_PLAYER_ID, WHITE_PLAYER_SHRINE_MASK,
+};
+use crate::move_generation::{
+    get_possible_moves_from_position, PreCalculated, VALID_MOVES_FROM_POSITION_MASKS,
+};
+use crate::neural_network::get_neural_network_score;
+use crate::wizard_race::{get_wizard_race_distance, MAX_WIZARD_RACE_DISTANCE};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvaluationBackend {
+    HandWritten,
+    NeuralNetwork,
+}
+
+pub static WHITE_EARLY_GAME_TARGET: i32 = 0b00000_00000_01110_01110_01110;
+
+pub static WHITE_MID_GAME_TARGET: i32 = 0b00000_01110_01110_01110_00000;
+
+pub static WHITE_END_GAME_TARGET: i32 = 0b01110_01110_01110_00000_00000;
+
+pub static BLACK_EARLY_GAME_TARGET: i32 = 0b01110_01110_01110_00000_00000;
+
+pub static BLACK_MID_GAME_TARGET: i32 = 0b00000_01110_01110_01110_00000;
+
+pub static BLACK_END_GAME_TARGET: i32 = 0b00000_00000_01110_01110_01110;
+
+pub static PIECE_POINTS: i32 = 100;
+
+pub static PREFERRED_POSITION_POINTS: i32 = 10;
+
+pub static WIND_SPIRIT_POINTS: i32 = 10;
+
+// the game targets move on to the mid game and the end game once the pieces left on the board
+// drop to these counts
+pub static MID_GAME_MAX_NUM_OF_PIECES: i32 = 8;
+
+pub static END_GAME_MAX_NUM_OF_PIECES: i32 = 4;
+
+// in the end game every move a wizard is ahead in the race to the opposing shrine is worth this
+// much, winning the race outright on top of it
+pub static WIZARD_RACE_POINTS_PER_MOVE: i32 = 10;
+
+pub static WIZARD_RACE_LEAD_POINTS: i32 = 30;
+
+pub static MOBILITY_POINTS_PER_MOVE: i32 = 2;
+
+pub static THREATENED_STUDENT_POINTS: i32 = 10;
+
+pub static THREATENED_WIZARD_POINTS: i32 = 30;
+
+pub static MIDDLE_CARD_THREAT_POINTS: i32 = 5;
+
+pub static GAME_TARGETS: [[i32; 3]; 2] = [
+    [
+        WHITE_EARLY_GAME_TARGET,
+        WHITE_MID_GAME_TARGET,
+        WHITE_END_GAME_TARGET,
+    ],
+    [
+        BLACK_EARLY_GAME_TARGET,
+        BLACK_MID_GAME_TARGET,
+        BLACK_END_GAME_TARGET,
+    ],
+];
+
+pub fn get_valid_moves_from_position_mask(position: i32) -> i32 {
+    return VALID_MOVES_FROM_POSITION_MASKS[position.trailing_zeros() as usize][1];
+}
+
+pub fn get_num_of_player_pieces(game_state: &GameState, player_id: usize) -> i32 {
+    let mut num_of_player_pieces = 0;
+    for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+        let piece_position = get_player_piece_position(game_state, player_id, piece_index);
+        if piece_position > 0 {
+            num_of_player_pieces += 1;
+        }
+    }
+    return num_of_player_pieces;
+}
+
+pub fn get_finished_game_score(game_state: &GameState) -> Option<i32> {
+    let white_wizard_position =
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    if white_wizard_position == 0 {
+        return Some(-100000);
+    }
+    if (white_wizard_position & BLACK_PLAYER_SHRINE_MASK) > 0 {
+        return Some(100000);
+    }
+    let black_wizard_position =
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    if black_wizard_position == 0 {
+        return Some(100000);
+    }
+    if (black_wizard_position & WHITE_PLAYER_SHRINE_MASK) > 0 {
+        return Some(-100000);
+    }
+    return None;
+}
+
+pub fn get_game_target_index(
+    num_of_total_pieces: i32,
+    engine_parameters: &EngineParameters,
+) -> usize {
+    if num_of_total_pieces > engine_parameters.mid_game_max_num_of_pieces {
+        return 0;
+    }
+    if num_of_total_pieces > engine_parameters.end_game_max_num_of_pieces {
+        return 1;
+    }
+    return 2;
+}
+
+pub fn get_game_state_score_with_parameters(
+    game_state: &GameState,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    if let Some(finished_game_score) = get_finished_game_score(game_state) {
+        return finished_game_score;
+    }
+    let white_wizard_position =
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    let black_wizard_position =
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    let num_of_white_pieces = get_num_of_player_pieces(game_state, WHITE_PLAYER_ID);
+    let num_of_black_pieces = get_num_of_player_pieces(game_state, BLACK_PLAYER_ID);
+
+    let points_from_num_of_pieces = num_of_white_pieces * engine_parameters.piece_points
+        - num_of_black_pieces * engine_parameters.piece_points;
+
+    let num_of_total_pieces = num_of_white_pieces + num_of_black_pieces;
+
+    let game_target_index = get_game_target_index(num_of_total_pieces, engine_parameters);
+    let white_player_target_mask =
+        engine_parameters.game_targets[WHITE_PLAYER_ID][game_target_index];
+    let black_player_target_mask =
+        engine_parameters.game_targets[BLACK_PLAYER_ID][game_target_index];
+
+    let white_player_pieces_bitmap = get_player_pieces_bitmask(game_state, WHITE_PLAYER_ID);
+    let black_player_pieces_bitmap = get_player_pieces_bitmask(game_state, BLACK_PLAYER_ID);
+
+    let num_of_white_pieces_matching_mask =
+        (white_player_pieces_bitmap & white_player_target_mask).count_ones() as i32;
+    let num_of_black_pieces_matching_mask =
+        (black_player_pieces_bitmap & black_player_target_mask).count_ones() as i32;
+
+    let points_from_pieces_in_preferred_position = num_of_white_pieces_matching_mask
+        * engine_parameters.preferred_position_points
+        - num_of_black_pieces_matching_mask * engine_parameters.preferred_position_points;
+
+    let wind_spirit_position = get_wind_spirit_position(game_state);
+    let mut points_from_wind_spirit = 0;
+    // a wind spirit within reach of a wizard blocks one of its escape squares
+    if wind_spirit_position > 0 {
+        if (get_valid_moves_from_position_mask(black_wizard_position) & wind_spirit_position) > 0 {
+            points_from_wind_spirit += engine_parameters.wind_spirit_points;
+        }
+        if (get_valid_moves_from_position_mask(white_wizard_position) & wind_spirit_position) > 0 {
+            points_from_wind_spirit -= engine_parameters.wind_spirit_points;
+        }
+    }
+
+    return points_from_num_of_pieces
+        + points_from_pieces_in_preferred_position
+        + points_from_wind_spirit;
+}
+
+pub fn get_game_state_score(game_state: &GameState) -> i32 {
+    return get_game_state_score_with_parameters(game_state, &DEFAULT_ENGINE_PARAMETERS);
+}
+
+pub fn get_game_score_for_maximizing_player(
+    game_state: &GameState,
+    maximizing_player_id: usize,
+) -> i32 {
+    let score = get_game_state_score(game_state);
+    if maximizing_player_id == WHITE_PLAYER_ID {
+        return score;
+    }
+    return -1 * score;
+}
+
+pub fn get_game_state_score_with_backend(
+    game_state: &GameState,
+    evaluation_backend: EvaluationBackend,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    if evaluation_backend == EvaluationBackend::HandWritten {
+        return get_game_state_score_with_parameters(game_state, engine_parameters);
+    }
+    if let Some(finished_game_score) = get_finished_game_score(game_state) {
+        return finished_game_score;
+    }
+    return get_neural_network_score(game_state);
+}
+
+pub fn get_game_score_for_maximizing_player_with_backend(
+    game_state: &GameState,
+    maximizing_player_id: usize,
+    evaluation_backend: EvaluationBackend,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    let score =
+        get_game_state_score_with_backend(game_state, evaluation_backend, engine_parameters);
+    if maximizing_player_id == WHITE_PLAYER_ID {
+        return score;
+    }
+    return -1 * score;
+}
+
+// every square a player's pieces reach with the given card, squares held by their own pieces
+// included, the caller masks those out
+fn get_squares_reached_with_card(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+    card: (i32, i32),
+) -> (i32, i32) {
+    let (card_id, card_rotation) = card;
+    let own_pieces_bitmap = get_player_pieces_bitmask(game_state, player_id);
+    let mut squares_reached = 0;
+    let mut num_of_moves = 0;
+    for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+        let piece_position = get_player_piece_position(game_state, player_id, piece_index);
+        if piece_position == 0 {
+            continue;
+        }
+        for piece_position_after_move in
+            get_possible_moves_from_position(pre_calculated, piece_position, card_id, card_rotation)
+                .iter()
+        {
+            if (piece_position_after_move & own_pieces_bitmap) > 0 {
+                continue;
+            }
+            squares_reached |= piece_position_after_move;
+            num_of_moves += 1;
+        }
+    }
+    return (squares_reached, num_of_moves);
+}
+
+fn get_threat_points(
+    game_state: &GameState,
+    player_id: usize,
+    squares_reached: i32,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    let opponent_id = get_opponent_id(player_id);
+    let opponent_wizard_position =
+        get_player_piece_position(game_state, opponent_id, GAME_STATE_WIZARD_OFFSET);
+    let threatened_students = squares_reached
+        & get_player_pieces_bitmask(game_state, opponent_id)
+        & !opponent_wizard_position;
+    let mut threat_points =
+        threatened_students.count_ones() as i32 * engine_parameters.threatened_student_points;
+    if (squares_reached & opponent_wizard_position) > 0 {
+        threat_points += engine_parameters.threatened_wizard_points;
+    }
+    return threat_points;
+}
+
+// cards flow from a player to the middle and on to the opponent, so next to the moves and threats
+// each side has with its own cards, the side to move is credited with the threats it picks up
+// from the middle card it is about to receive
+pub fn get_card_flow_score(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id_to_move: usize,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    let mut player_scores: [i32; 2] = [0; 2];
+    let mut player_squares_reached: [i32; 2] = [0; 2];
+    for player_id in 0..NUM_OF_PLAYERS {
+        let mut num_of_moves = 0;
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            let (squares_reached, num_of_moves_with_card) = get_squares_reached_with_card(
+                game_state,
+                pre_calculated,
+                player_id,
+                get_player_card(game_state, player_id, card_index),
+            );
+            player_squares_reached[player_id] |= squares_reached;
+            num_of_moves += num_of_moves_with_card;
+        }
+        player_scores[player_id] = num_of_moves * engine_parameters.mobility_points_per_move
+            + get_threat_points(
+                game_state,
+                player_id,
+                player_squares_reached[player_id],
+                engine_parameters,
+            );
+    }
+
+    // the middle card is already turned towards the player who is going to receive it
+    let (middle_card_squares_reached, _) = get_squares_reached_with_card(
+        game_state,
+        pre_calculated,
+        player_id_to_move,
+        get_middle_card(game_state),
+    );
+    let opponent_pieces_bitmap =
+        get_player_pieces_bitmask(game_state, get_opponent_id(player_id_to_move));
+    let num_of_new_threats = (middle_card_squares_reached
+        & !player_squares_reached[player_id_to_move]
+        & opponent_pieces_bitmap)
+        .count_ones() as i32;
+    player_scores[player_id_to_move] +=
+        num_of_new_threats * engine_parameters.middle_card_threat_points;
+
+    return player_scores[WHITE_PLAYER_ID] - player_scores[BLACK_PLAYER_ID];
+}
+
+// only the end game is a race, earlier on there are too many pieces in the way, the side to move
+// wins a race of equal length
+pub fn get_wizard_race_score(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id_to_move: usize,
+    engine_parameters: &EngineParameters,
+) -> i32 {
+    let num_of_total_pieces = get_num_of_player_pieces(game_state, WHITE_PLAYER_ID)
+        + get_num_of_player_pieces(game_state, BLACK_PLAYER_ID);
+    if get_game_target_index(num_of_total_pieces, engine_parameters) != 2 {
+        return 0;
+    }
+    let opponent_id = get_opponent_id(player_id_to_move);
+    let (middle_card_id, _) = get_middle_card(game_state);
+    let distance = get_wizard_race_distance(
+        game_state,
+        pre_calculated,
+        player_id_to_move,
+        get_player_piece_position(game_state, player_id_to_move, GAME_STATE_WIZARD_OFFSET),
+        middle_card_id,
+    );
+    // the opponent's middle card is whichever card the side to move gives up
+    let opponent_distance = (0..NUM_OF_CARDS_PER_PLAYER)
+        .map(|card_index| {
+            get_wizard_race_distance(
+                game_state,
+                pre_calculated,
+                opponent_id,
+                get_player_piece_position(game_state, opponent_id, GAME_STATE_WIZARD_OFFSET),
+                get_player_card(game_state, player_id_to_move, card_index).0,
+            )
+        })
+        .min()
+        .unwrap_or(MAX_WIZARD_RACE_DISTANCE);
+
+    let mut score = (opponent_distance - distance) * engine_parameters.wizard_race_points_per_move;
+    if distance.min(opponent_distance) < MAX_WIZARD_RACE_DISTANCE {
+        if distance <= opponent_distance {
+            score += engine_parameters.wizard_race_lead_points;
+        } else {
+            score -= engine_parameters.wizard_race_lead_points;
+        }
+    }
+    if player_id_to_move == WHITE_PLAYER_ID {
+        return score;
+    }
+    return -1 * score;
+}
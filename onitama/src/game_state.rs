@@ -0,0 +1,571 @@
+pub static NUM_OF_TABLE_ROWS: usize = 5;
+
+pub static NUM_OF_TABLE_COLS: usize = 5;
+
+pub static NUM_OF_PLAYERS: usize = 2;
+
+pub static NUM_OF_CARDS: usize = 5;
+
+pub static NUM_OF_STATES_PER_PLAYER: usize = 10;
+
+pub static NUM_OF_PIECES_PER_PLAYER: usize = 5;
+
+pub static NUM_OF_STUDENTS_PER_PLAYER: usize = 4;
+
+pub static NUM_OF_CARDS_PER_PLAYER: usize = 2;
+
+pub static NUM_OF_MOVES_PER_CARD: usize = 4;
+
+pub static DEFAULT_CARD_ROTATION: i32 = 1;
+
+pub static WHITE_PLAYER_ID: usize = 0;
+
+pub static BLACK_PLAYER_ID: usize = 1;
+
+pub static GAME_STATE_STUDENTS_OFFSET: usize = 0;
+
+pub static GAME_STATE_WIZARD_OFFSET: usize = 4;
+
+pub static GAME_STATE_CARDS_OFFSET: usize = 5;
+
+pub static GAME_STATE_PLAYER_PIECES_POSITION_BITMAP_OFFSET: usize = 9;
+
+pub static GAME_STATE_MIDDLE_CARD_OFFSET: usize = 20;
+
+pub static GAME_STATE_WIND_SPIRIT_OFFSET: usize = 22;
+
+pub static WIND_SPIRIT_PIECE_INDEX: usize = 5;
+
+pub static WHITE_PLAYER_SHRINE_MASK: i32 = 0b00000_00000_00000_00000_00100;
+
+pub static BLACK_PLAYER_SHRINE_MASK: i32 = 0b00100_00000_00000_00000_00000;
+
+pub static INITIAL_WIND_SPIRIT_POSITION: i32 = 0b00000_00000_00100_00000_00000;
+
+pub static INITIAL_WHITE_STUDENT_POSITIONS: [i32; 4] = [
+    0b00000_00000_00000_00000_00001,
+    0b00000_00000_00000_00000_00010,
+    0b00000_00000_00000_00000_01000,
+    0b00000_00000_00000_00000_10000,
+];
+
+pub static INITIAL_BLACK_STUDENT_POSITIONS: [i32; 4] = [
+    0b00001_00000_00000_00000_00000,
+    0b00010_00000_00000_00000_00000,
+    0b01000_00000_00000_00000_00000,
+    0b10000_00000_00000_00000_00000,
+];
+
+pub type GameState = [i32; 23];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RulesVariant {
+    Base,
+    WayOfTheWind,
+}
+
+// the names the engine protocol's position command and the command line take
+pub fn get_rules_variant_name(rules_variant: RulesVariant) -> &'static str {
+    return match rules_variant {
+        RulesVariant::Base => "base",
+        RulesVariant::WayOfTheWind => "wind",
+    };
+}
+
+pub fn coordinates_to_bitwise_shift(x: i32, y: i32) -> i32 {
+    return x + y * 5;
+}
+
+pub fn shift_position(position: i32, shift_by: i32) -> i32 {
+    if shift_by > 0 {
+        return position << shift_by;
+    }
+    if shift_by < 0 {
+        return position >> (-1 * shift_by);
+    }
+    return position;
+}
+
+pub fn get_opponent_id(player_id: usize) -> usize {
+    if player_id == WHITE_PLAYER_ID {
+        return BLACK_PLAYER_ID;
+    }
+    return WHITE_PLAYER_ID;
+}
+
+pub fn get_player_offset(player_id: usize) -> usize {
+    return player_id * NUM_OF_STATES_PER_PLAYER;
+}
+
+pub fn get_player_piece_index(player_id: usize, piece_index: usize) -> usize {
+    return get_player_offset(player_id) + piece_index;
+}
+
+pub fn get_player_piece_position(
+    game_state: &GameState,
+    player_id: usize,
+    piece_index: usize,
+) -> i32 {
+    return game_state[get_player_piece_index(player_id, piece_index)];
+}
+
+pub fn get_player_card_info_indexes(player_id: usize, card_index: usize) -> (usize, usize) {
+    let player_card_index = get_player_offset(player_id) + GAME_STATE_CARDS_OFFSET + card_index * 2;
+    return (player_card_index, player_card_index + 1);
+}
+
+pub fn get_middle_card_info_indexes() -> (usize, usize) {
+    return (
+        GAME_STATE_MIDDLE_CARD_OFFSET,
+        GAME_STATE_MIDDLE_CARD_OFFSET + 1,
+    );
+}
+
+pub fn get_player_card(game_state: &GameState, player_id: usize, card_index: usize) -> (i32, i32) {
+    let (player_card_id_index, player_card_rotation_index) =
+        get_player_card_info_indexes(player_id, card_index);
+    return (
+        game_state[player_card_id_index],
+        game_state[player_card_rotation_index],
+    );
+}
+
+pub fn get_player_pieces_bitmask(game_state: &GameState, player_id: usize) -> i32 {
+    return game_state
+        [get_player_offset(player_id) + GAME_STATE_PLAYER_PIECES_POSITION_BITMAP_OFFSET];
+}
+
+pub fn set_player_piece_position(
+    game_state: &mut GameState,
+    player_id: usize,
+    piece_index: usize,
+    piece_position: i32,
+) {
+    game_state[get_player_piece_index(player_id, piece_index)] = piece_position;
+}
+
+pub fn set_player_pieces_bitmask(game_state: &mut GameState, player_id: usize, bitmap: i32) {
+    game_state[get_player_offset(player_id) + GAME_STATE_PLAYER_PIECES_POSITION_BITMAP_OFFSET] =
+        bitmap;
+}
+
+pub fn set_player_card(
+    game_state: &mut GameState,
+    player_id: usize,
+    card_index: usize,
+    card_id: i32,
+    card_rotation: i32,
+) {
+    let (player_card_id_index, player_card_rotation_index) =
+        get_player_card_info_indexes(player_id, card_index);
+    game_state[player_card_id_index] = card_id;
+    game_state[player_card_rotation_index] = card_rotation;
+}
+
+pub fn set_middle_card(game_state: &mut GameState, card_id: i32, card_rotation: i32) {
+    let (middle_card_id_index, middle_card_rotation_index) = get_middle_card_info_indexes();
+    game_state[middle_card_id_index] = card_id;
+    game_state[middle_card_rotation_index] = card_rotation;
+}
+
+pub fn get_wind_spirit_position(game_state: &GameState) -> i32 {
+    return game_state[GAME_STATE_WIND_SPIRIT_OFFSET];
+}
+
+pub fn set_wind_spirit_position(game_state: &mut GameState, wind_spirit_position: i32) {
+    game_state[GAME_STATE_WIND_SPIRIT_OFFSET] = wind_spirit_position;
+}
+
+pub fn is_wizard_at_position(game_state: &GameState, position: i32) -> bool {
+    let white_wizard_position =
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    let black_wizard_position =
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    return ((white_wizard_position | black_wizard_position) & position) > 0;
+}
+
+pub fn is_player_moving_on_own_piece(
+    game_state: &GameState,
+    player_id: usize,
+    player_move: i32,
+) -> bool {
+    let own_pieces_bitmap = get_player_pieces_bitmask(game_state, player_id);
+    return (player_move & own_pieces_bitmap) > 0;
+}
+
+pub fn re_clculate_player_pieces_bitmap(game_state: &mut GameState) {
+    for player_id in 0..NUM_OF_PLAYERS {
+        let mut bitmask = 0;
+        for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+            bitmask |= get_player_piece_position(game_state, player_id, piece_index);
+        }
+        set_player_pieces_bitmask(game_state, player_id, bitmask);
+    }
+}
+
+pub fn apply_player_move_to_opponent_pieces(
+    game_state: &mut GameState,
+    player_id: usize,
+    player_move: i32,
+) {
+    let opponent_id = get_opponent_id(player_id);
+    let opponent_pieces_bitmap = get_player_pieces_bitmask(game_state, opponent_id);
+
+    if (player_move & opponent_pieces_bitmap) == 0 {
+        return;
+    }
+
+    for opponent_piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+        let opponent_piece_position =
+            get_player_piece_position(game_state, opponent_id, opponent_piece_index);
+        if (player_move & opponent_piece_position) == 0 {
+            continue;
+        }
+        set_player_piece_position(game_state, opponent_id, opponent_piece_index, 0);
+        return;
+    }
+}
+
+pub fn move_player_piece(
+    game_state: &mut GameState,
+    player_id: usize,
+    piece_index: usize,
+    piece_position_after_move: i32,
+) {
+    set_player_piece_position(
+        game_state,
+        player_id,
+        piece_index,
+        piece_position_after_move,
+    );
+    apply_player_move_to_opponent_pieces(game_state, player_id, piece_position_after_move);
+}
+
+// the wind spirit never captures, a student standing on its target swaps places with it
+pub fn move_wind_spirit(game_state: &mut GameState, wind_spirit_position_after_move: i32) {
+    let wind_spirit_position_before_move = get_wind_spirit_position(game_state);
+    for player_id in 0..NUM_OF_PLAYERS {
+        for student_index in GAME_STATE_STUDENTS_OFFSET..GAME_STATE_WIZARD_OFFSET {
+            let student_position = get_player_piece_position(game_state, player_id, student_index);
+            if student_position != wind_spirit_position_after_move {
+                continue;
+            }
+            set_player_piece_position(
+                game_state,
+                player_id,
+                student_index,
+                wind_spirit_position_before_move,
+            );
+        }
+    }
+    set_wind_spirit_position(game_state, wind_spirit_position_after_move);
+}
+
+pub fn get_middle_card(game_state: &GameState) -> (i32, i32) {
+    let (middle_card_id_index, middle_card_rotation_index) = get_middle_card_info_indexes();
+    return (
+        game_state[middle_card_id_index],
+        game_state[middle_card_rotation_index],
+    );
+}
+
+pub fn is_game_finished(game_state: &GameState) -> bool {
+    let white_wizard_position =
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    let black_wizard_position =
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    return (white_wizard_position == 0)
+        || ((white_wizard_position & BLACK_PLAYER_SHRINE_MASK) > 0)
+        || (black_wizard_position == 0)
+        || ((black_wizard_position & WHITE_PLAYER_SHRINE_MASK) > 0);
+}
+
+pub fn get_winner_id(game_state: &GameState) -> Option<usize> {
+    let white_wizard_position =
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    let black_wizard_position =
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET);
+    if black_wizard_position == 0 || (white_wizard_position & BLACK_PLAYER_SHRINE_MASK) > 0 {
+        return Some(WHITE_PLAYER_ID);
+    }
+    if white_wizard_position == 0 || (black_wizard_position & WHITE_PLAYER_SHRINE_MASK) > 0 {
+        return Some(BLACK_PLAYER_ID);
+    }
+    return None;
+}
+
+// every player has four student slots and one wizard slot, a board with more pieces than that
+// cannot be stored and is rejected before anything is written
+pub fn check_board_piece_counts<'a>(
+    board_rows: impl Iterator<Item = &'a str>,
+) -> Result<(), String> {
+    let mut num_of_students: [usize; 2] = [0; 2];
+    let mut num_of_wizards: [usize; 2] = [0; 2];
+    for board_row in board_rows {
+        for cell in board_row.chars() {
+            match cell {
+                'w' => num_of_students[WHITE_PLAYER_ID] += 1,
+                'b' => num_of_students[BLACK_PLAYER_ID] += 1,
+                'W' => num_of_wizards[WHITE_PLAYER_ID] += 1,
+                'B' => num_of_wizards[BLACK_PLAYER_ID] += 1,
+                _ => {}
+            }
+        }
+    }
+    for (player_id, player_name) in [(WHITE_PLAYER_ID, "white"), (BLACK_PLAYER_ID, "black")].iter()
+    {
+        if num_of_students[*player_id] > NUM_OF_STUDENTS_PER_PLAYER {
+            return Err(format!(
+                "{} has {} students, at most {} fit on the board",
+                player_name, num_of_students[*player_id], NUM_OF_STUDENTS_PER_PLAYER
+            ));
+        }
+        if num_of_wizards[*player_id] > 1 {
+            return Err(format!(
+                "{} has {} wizards, expected at most 1",
+                player_name, num_of_wizards[*player_id]
+            ));
+        }
+    }
+    return Ok(());
+}
+
+// students past the four slots are left off instead of overrunning the wizard's, boards from
+// outside are expected to have gone through check_board_piece_counts or the turn parser first
+pub fn set_pieces_from_board_rows(
+    game_state: &mut GameState,
+    board_rows: &[String],
+    rules_variant: RulesVariant,
+) {
+    let mut b_student_index = 0;
+    let mut w_student_index = 0;
+
+    for (i, board) in board_rows.iter().enumerate().take(NUM_OF_TABLE_COLS) {
+        let y: i32 = 4 - i as i32;
+        for k in 0..NUM_OF_TABLE_ROWS {
+            let cell = board.chars().nth(k).unwrap();
+            let x = k as i32;
+            let piece_position = shift_position(1, coordinates_to_bitwise_shift(x, y));
+            match cell {
+                'W' => {
+                    set_player_piece_position(
+                        game_state,
+                        WHITE_PLAYER_ID,
+                        GAME_STATE_WIZARD_OFFSET,
+                        piece_position,
+                    );
+                }
+                'w' if w_student_index < NUM_OF_STUDENTS_PER_PLAYER => {
+                    set_player_piece_position(
+                        game_state,
+                        WHITE_PLAYER_ID,
+                        GAME_STATE_STUDENTS_OFFSET + w_student_index,
+                        piece_position,
+                    );
+                    w_student_index += 1;
+                }
+                'B' => {
+                    set_player_piece_position(
+                        game_state,
+                        BLACK_PLAYER_ID,
+                        GAME_STATE_WIZARD_OFFSET,
+                        piece_position,
+                    );
+                }
+                'b' if b_student_index < NUM_OF_STUDENTS_PER_PLAYER => {
+                    set_player_piece_position(
+                        game_state,
+                        BLACK_PLAYER_ID,
+                        GAME_STATE_STUDENTS_OFFSET + b_student_index,
+                        piece_position,
+                    );
+                    b_student_index += 1;
+                }
+                'S' if rules_variant == RulesVariant::WayOfTheWind => {
+                    set_wind_spirit_position(game_state, piece_position);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// the catalogue describes cards from white's side of the board, so white holds its cards
+// unrotated, black holds them rotated and the middle card faces the player who moves first
+pub fn set_cards_from_catalogue(
+    game_state: &mut GameState,
+    card_ids: &[i32; 5],
+    first_player_id: usize,
+) {
+    set_player_card(
+        game_state,
+        WHITE_PLAYER_ID,
+        0,
+        card_ids[0],
+        DEFAULT_CARD_ROTATION,
+    );
+    set_player_card(
+        game_state,
+        WHITE_PLAYER_ID,
+        1,
+        card_ids[1],
+        DEFAULT_CARD_ROTATION,
+    );
+    set_player_card(
+        game_state,
+        BLACK_PLAYER_ID,
+        0,
+        card_ids[2],
+        -1 * DEFAULT_CARD_ROTATION,
+    );
+    set_player_card(
+        game_state,
+        BLACK_PLAYER_ID,
+        1,
+        card_ids[3],
+        -1 * DEFAULT_CARD_ROTATION,
+    );
+    let middle_card_rotation = if first_player_id == WHITE_PLAYER_ID {
+        DEFAULT_CARD_ROTATION
+    } else {
+        -1 * DEFAULT_CARD_ROTATION
+    };
+    set_middle_card(game_state, card_ids[4], middle_card_rotation);
+}
+
+pub fn create_initial_game_state(
+    card_ids: &[i32; 5],
+    first_player_id: usize,
+    rules_variant: RulesVariant,
+) -> GameState {
+    let mut game_state: GameState = [0; 23];
+    for (student_index, student_position) in INITIAL_WHITE_STUDENT_POSITIONS.iter().enumerate() {
+        set_player_piece_position(
+            &mut game_state,
+            WHITE_PLAYER_ID,
+            GAME_STATE_STUDENTS_OFFSET + student_index,
+            *student_position,
+        );
+    }
+    for (student_index, student_position) in INITIAL_BLACK_STUDENT_POSITIONS.iter().enumerate() {
+        set_player_piece_position(
+            &mut game_state,
+            BLACK_PLAYER_ID,
+            GAME_STATE_STUDENTS_OFFSET + student_index,
+            *student_position,
+        );
+    }
+    set_player_piece_position(
+        &mut game_state,
+        WHITE_PLAYER_ID,
+        GAME_STATE_WIZARD_OFFSET,
+        WHITE_PLAYER_SHRINE_MASK,
+    );
+    set_player_piece_position(
+        &mut game_state,
+        BLACK_PLAYER_ID,
+        GAME_STATE_WIZARD_OFFSET,
+        BLACK_PLAYER_SHRINE_MASK,
+    );
+    set_cards_from_catalogue(&mut game_state, card_ids, first_player_id);
+    if rules_variant == RulesVariant::WayOfTheWind {
+        set_wind_spirit_position(&mut game_state, INITIAL_WIND_SPIRIT_POSITION);
+    }
+    re_clculate_player_pieces_bitmap(&mut game_state);
+    return game_state;
+}
+
+// compact notation, board rows from rank 5 down to rank 1 separated by '/', e.g. the opening is
+// "bbBbb/-----/-----/-----/wwWww"
+pub fn create_game_state_from_board(
+    board: &str,
+    card_ids: &[i32; 5],
+    player_id_to_move: usize,
+    rules_variant: RulesVariant,
+) -> GameState {
+    let mut game_state: GameState = [0; 23];
+    let board_rows: Vec<String> = board.split('/').map(|v| v.to_string()).collect();
+    set_pieces_from_board_rows(&mut game_state, &board_rows, rules_variant);
+    set_cards_from_catalogue(&mut game_state, card_ids, player_id_to_move);
+    re_clculate_player_pieces_bitmap(&mut game_state);
+    return game_state;
+}
+
+// the compact notation create_game_state_from_board reads
+pub fn get_board_from_game_state(game_state: &GameState) -> String {
+    let mut board_rows: Vec<String> = vec![];
+    for y in (0..NUM_OF_TABLE_ROWS as i32).rev() {
+        let mut board_row = String::new();
+        for x in 0..NUM_OF_TABLE_COLS as i32 {
+            let position = shift_position(1, coordinates_to_bitwise_shift(x, y));
+            let cell = if (get_player_pieces_bitmask(game_state, WHITE_PLAYER_ID) & position) > 0 {
+                if is_wizard_at_position(game_state, position) {
+                    'W'
+                } else {
+                    'w'
+                }
+            } else if (get_player_pieces_bitmask(game_state, BLACK_PLAYER_ID) & position) > 0 {
+                if is_wizard_at_position(game_state, position) {
+                    'B'
+                } else {
+                    'b'
+                }
+            } else if (get_wind_spirit_position(game_state) & position) > 0 {
+                'S'
+            } else {
+                '-'
+            };
+            board_row.push(cell);
+        }
+        board_rows.push(board_row);
+    }
+    return board_rows.join("/");
+}
+
+// turning the board by 180 degrees moves the cell at bit i to bit 24 - i
+pub fn get_mirrored_position(position: i32) -> i32 {
+    return ((position as u32).reverse_bits() >> 7) as i32;
+}
+
+// swaps the colours and turns the board around, the cards change hands and face the other way, so
+// the mirrored position is the same game seen from the other side of the table
+pub fn get_mirrored_game_state(game_state: &GameState) -> GameState {
+    let mut mirrored_game_state: GameState = [0; 23];
+    for player_id in 0..NUM_OF_PLAYERS {
+        let opponent_id = get_opponent_id(player_id);
+        for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+            set_player_piece_position(
+                &mut mirrored_game_state,
+                opponent_id,
+                piece_index,
+                get_mirrored_position(get_player_piece_position(
+                    game_state,
+                    player_id,
+                    piece_index,
+                )),
+            );
+        }
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            let (card_id, card_rotation) = get_player_card(game_state, player_id, card_index);
+            set_player_card(
+                &mut mirrored_game_state,
+                opponent_id,
+                card_index,
+                card_id,
+                -1 * card_rotation,
+            );
+        }
+    }
+    let (middle_card_id, middle_card_rotation) = get_middle_card(game_state);
+    set_middle_card(
+        &mut mirrored_game_state,
+        middle_card_id,
+        -1 * middle_card_rotation,
+    );
+    set_wind_spirit_position(
+        &mut mirrored_game_state,
+        get_mirrored_position(get_wind_spirit_position(game_state)),
+    );
+    re_clculate_player_pieces_bitmap(&mut mirrored_game_state);
+    return mirrored_game_state;
+}
@@ -0,0 +1,82 @@
+use std::cmp;
+
+use crate::game_state::{
+    get_middle_card, get_player_card, get_player_piece_position, get_player_pieces_bitmask,
+    get_wind_spirit_position, GameState, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET,
+    WHITE_PLAYER_ID,
+};
+
+pub type PositionKey = [i32; 11];
+
+#[derive(Debug, Clone, Copy)]
+pub struct DrawRule {
+    pub max_num_of_repetitions: usize,
+    pub max_num_of_turns: usize,
+}
+
+pub static DEFAULT_DRAW_RULE: DrawRule = DrawRule {
+    max_num_of_repetitions: 3,
+    max_num_of_turns: 200,
+};
+
+#[derive(Debug, Clone)]
+pub struct GameHistory {
+    pub draw_rule: DrawRule,
+    pub num_of_turns_played: usize,
+    pub position_keys: Vec<PositionKey>,
+}
+
+pub fn get_position_key(game_state: &GameState, player_id_to_move: usize) -> PositionKey {
+    let (white_card_id_1, _) = get_player_card(game_state, WHITE_PLAYER_ID, 0);
+    let (white_card_id_2, _) = get_player_card(game_state, WHITE_PLAYER_ID, 1);
+    let (black_card_id_1, _) = get_player_card(game_state, BLACK_PLAYER_ID, 0);
+    let (black_card_id_2, _) = get_player_card(game_state, BLACK_PLAYER_ID, 1);
+    let (middle_card_id, _) = get_middle_card(game_state);
+    return [
+        get_player_pieces_bitmask(game_state, WHITE_PLAYER_ID),
+        get_player_pieces_bitmask(game_state, BLACK_PLAYER_ID),
+        get_player_piece_position(game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET),
+        get_player_piece_position(game_state, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET),
+        cmp::min(white_card_id_1, white_card_id_2),
+        cmp::max(white_card_id_1, white_card_id_2),
+        cmp::min(black_card_id_1, black_card_id_2),
+        cmp::max(black_card_id_1, black_card_id_2),
+        middle_card_id,
+        get_wind_spirit_position(game_state),
+        player_id_to_move as i32,
+    ];
+}
+
+pub fn create_game_history(draw_rule: DrawRule) -> GameHistory {
+    return GameHistory {
+        draw_rule,
+        num_of_turns_played: 0,
+        position_keys: vec![],
+    };
+}
+
+pub fn get_num_of_position_repetitions(
+    game_history: &GameHistory,
+    position_key: &PositionKey,
+) -> usize {
+    return game_history
+        .position_keys
+        .iter()
+        .filter(|v| *v == position_key)
+        .count();
+}
+
+pub fn is_draw_by_turn_limit(game_history: &GameHistory, num_of_turns_ahead: usize) -> bool {
+    return game_history.num_of_turns_played + num_of_turns_ahead
+        >= game_history.draw_rule.max_num_of_turns;
+}
+
+pub fn is_draw_by_repetition(game_history: &GameHistory) -> bool {
+    return match game_history.position_keys.last() {
+        Some(position_key) => {
+            get_num_of_position_repetitions(game_history, position_key)
+                >= game_history.draw_rule.max_num_of_repetitions
+        }
+        None => false,
+    };
+}
@@ -0,0 +1,22 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+pub mod bench;
+pub mod cards;
+pub mod cli;
+pub mod codingame;
+pub mod dataset;
+pub mod engine_parameters;
+pub mod engine_protocol;
+pub mod evaluation;
+pub mod game_state;
+pub mod history;
+pub mod move_generation;
+pub mod neural_network;
+pub mod neural_network_weights;
+pub mod opponent_model;
+pub mod parser;
+pub mod referee;
+pub mod search;
+pub mod stats;
+pub mod tree_export;
+pub mod wizard_race;
@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+
+use crate::cards::{get_card_moves_from_catalogue, CardMoves, CardMovesMap};
+use crate::game_state::{
+    coordinates_to_bitwise_shift, get_middle_card, get_opponent_id, get_player_card,
+    get_player_piece_position, get_wind_spirit_position, is_game_finished,
+    is_player_moving_on_own_piece, is_wizard_at_position, move_player_piece, move_wind_spirit,
+    re_clculate_player_pieces_bitmap, set_middle_card, set_player_card, shift_position, GameState,
+    DEFAULT_CARD_ROTATION, NUM_OF_CARDS_PER_PLAYER, NUM_OF_MOVES_PER_CARD,
+    NUM_OF_PIECES_PER_PLAYER, NUM_OF_TABLE_COLS, NUM_OF_TABLE_ROWS, WIND_SPIRIT_PIECE_INDEX,
+};
+use crate::wizard_race::{create_wizard_race_table, WizardRaceTable};
+
+pub static VALID_MOVES_FROM_POSITION_MASKS: [[i32; 2]; 25] = [
+    [1, 0b00000_00000_00111_00111_00111],
+    [2, 0b00000_00000_01111_01111_01111],
+    [4, 0b00000_00000_11111_11111_11111],
+    [8, 0b00000_00000_11110_11110_11110],
+    [16, 0b00000_00000_11100_11100_11100],
+    [32, 0b00000_00111_00111_00111_00111],
+    [64, 0b00000_01111_01111_01111_01111],
+    [128, 0b00000_11111_11111_11111_11111],
+    [256, 0b00000_11110_11110_11110_11110],
+    [512, 0b00000_11100_11100_11100_11100],
+    [1024, 0b00111_00111_00111_00111_00111],
+    [2048, 0b01111_01111_01111_01111_01111],
+    [4096, 0b11111_11111_11111_11111_11111],
+    [8192, 0b11110_11110_11110_11110_11110],
+    [16384, 0b11100_11100_11100_11100_11100],
+    [32768, 0b00111_00111_00111_00111_00000],
+    [65536, 0b01111_01111_01111_01111_00000],
+    [131072, 0b11111_11111_11111_11111_00000],
+    [262144, 0b11110_11110_11110_11110_00000],
+    [524288, 0b11100_11100_11100_11100_00000],
+    [1048576, 0b00111_00111_00111_00000_00000],
+    [2097152, 0b01111_01111_01111_00000_00000],
+    [4194304, 0b11111_11111_11111_00000_00000],
+    [8388608, 0b11110_11110_11110_00000_00000],
+    [16777216, 0b11100_11100_11100_00000_00000],
+];
+
+pub static BOARD_BIT_VALUES_TO_CELLS: [(i32, &str); 25] = [
+    (1, "A1"),
+    (2, "B1"),
+    (4, "C1"),
+    (8, "D1"),
+    (16, "E1"),
+    (32, "A2"),
+    (64, "B2"),
+    (128, "C2"),
+    (256, "D2"),
+    (512, "E2"),
+    (1024, "A3"),
+    (2048, "B3"),
+    (4096, "C3"),
+    (8192, "D3"),
+    (16384, "E3"),
+    (32768, "A4"),
+    (65536, "B4"),
+    (131072, "C4"),
+    (262144, "D4"),
+    (524288, "E4"),
+    (1048576, "A5"),
+    (2097152, "B5"),
+    (4194304, "C5"),
+    (8388608, "D5"),
+    (16777216, "E5"),
+];
+
+// the piece index of a move that only exchanges the card, see get_pass_moves
+pub static PASS_PIECE_INDEX: usize = 6;
+
+pub static PASS_COMMAND: &str = "PASS";
+
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerMove {
+    pub card_index: usize,
+    pub piece_index: usize,
+    pub piece_position_before_move: i32,
+    pub piece_position_after_move: i32,
+    pub wind_spirit_position_before_move: i32,
+    pub wind_spirit_position_after_move: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreCalculated {
+    pub valid_moves_from_position_masks: HashMap<i32, i32>,
+    pub board_bit_values_to_cells: HashMap<i32, String>,
+    pub positions_after_card_move_map: HashMap<i32, HashMap<i32, Vec<i32>>>,
+    pub positions_after_rotated_card_move_map: HashMap<i32, HashMap<i32, Vec<i32>>>,
+    pub wind_card_ids: Vec<i32>,
+    pub wind_spirit_positions_after_card_move_map: HashMap<i32, HashMap<i32, Vec<i32>>>,
+    pub wind_spirit_positions_after_rotated_card_move_map: HashMap<i32, HashMap<i32, Vec<i32>>>,
+    pub wizard_race_table: WizardRaceTable,
+}
+
+pub fn get_possible_moves_from_position(
+    pre_calculated: &PreCalculated,
+    piece_position_before_move: i32,
+    card_id: i32,
+    card_rotation: i32,
+) -> &Vec<i32> {
+    if card_rotation == DEFAULT_CARD_ROTATION {
+        return pre_calculated
+            .positions_after_card_move_map
+            .get(&piece_position_before_move)
+            .unwrap()
+            .get(&card_id)
+            .unwrap();
+    }
+    return pre_calculated
+        .positions_after_rotated_card_move_map
+        .get(&piece_position_before_move)
+        .unwrap()
+        .get(&card_id)
+        .unwrap();
+}
+
+pub fn get_possible_wind_spirit_moves_from_position(
+    pre_calculated: &PreCalculated,
+    wind_spirit_position_before_move: i32,
+    card_id: i32,
+    card_rotation: i32,
+) -> &Vec<i32> {
+    if card_rotation == DEFAULT_CARD_ROTATION {
+        return pre_calculated
+            .wind_spirit_positions_after_card_move_map
+            .get(&wind_spirit_position_before_move)
+            .unwrap()
+            .get(&card_id)
+            .unwrap();
+    }
+    return pre_calculated
+        .wind_spirit_positions_after_rotated_card_move_map
+        .get(&wind_spirit_position_before_move)
+        .unwrap()
+        .get(&card_id)
+        .unwrap();
+}
+
+pub fn is_wind_card(pre_calculated: &PreCalculated, card_id: i32) -> bool {
+    return pre_calculated.wind_card_ids.contains(&card_id);
+}
+
+fn get_possible_piece_moves(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+) -> Vec<PlayerMove> {
+    let mut player_moves: Vec<PlayerMove> = vec![];
+    let wind_spirit_position = get_wind_spirit_position(game_state);
+    for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+        let piece_position_before_move =
+            get_player_piece_position(game_state, player_id, piece_index);
+        if piece_position_before_move == 0 {
+            continue;
+        }
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            let (card_id, card_rotation) = get_player_card(game_state, player_id, card_index);
+
+            let piece_positions_after_move = get_possible_moves_from_position(
+                pre_calculated,
+                piece_position_before_move,
+                card_id,
+                card_rotation,
+            );
+
+            for piece_position_after_move_ in piece_positions_after_move.iter() {
+                let piece_position_after_move = *piece_position_after_move_;
+
+                if is_player_moving_on_own_piece(game_state, player_id, piece_position_after_move)
+                    || (piece_position_after_move & wind_spirit_position) > 0
+                {
+                    continue;
+                }
+
+                let player_move = PlayerMove {
+                    card_index,
+                    piece_index,
+                    piece_position_before_move,
+                    piece_position_after_move,
+                    wind_spirit_position_before_move: 0,
+                    wind_spirit_position_after_move: 0,
+                };
+
+                if wind_spirit_position == 0 || !is_wind_card(pre_calculated, card_id) {
+                    player_moves.push(player_move);
+                    continue;
+                }
+
+                // a wind card has to move the wind spirit as well whenever it can
+                let mut game_state_after_piece_move = *game_state;
+                move_player_piece(
+                    &mut game_state_after_piece_move,
+                    player_id,
+                    piece_index,
+                    piece_position_after_move,
+                );
+                let num_of_player_moves_before_wind_spirit = player_moves.len();
+                let wind_spirit_positions_after_move = get_possible_wind_spirit_moves_from_position(
+                    pre_calculated,
+                    wind_spirit_position,
+                    card_id,
+                    card_rotation,
+                );
+                for wind_spirit_position_after_move_ in wind_spirit_positions_after_move.iter() {
+                    let wind_spirit_position_after_move = *wind_spirit_position_after_move_;
+                    if is_wizard_at_position(
+                        &game_state_after_piece_move,
+                        wind_spirit_position_after_move,
+                    ) {
+                        continue;
+                    }
+                    player_moves.push(PlayerMove {
+                        wind_spirit_position_before_move: wind_spirit_position,
+                        wind_spirit_position_after_move,
+                        ..player_move
+                    });
+                }
+                if player_moves.len() == num_of_player_moves_before_wind_spirit {
+                    player_moves.push(player_move);
+                }
+            }
+        }
+    }
+
+    if wind_spirit_position == 0 {
+        return player_moves;
+    }
+
+    // regular cards can move the wind spirit instead of one of the player's own pieces
+    for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+        let (card_id, card_rotation) = get_player_card(game_state, player_id, card_index);
+        if is_wind_card(pre_calculated, card_id) {
+            continue;
+        }
+        let wind_spirit_positions_after_move = get_possible_moves_from_position(
+            pre_calculated,
+            wind_spirit_position,
+            card_id,
+            card_rotation,
+        );
+        for wind_spirit_position_after_move_ in wind_spirit_positions_after_move.iter() {
+            let wind_spirit_position_after_move = *wind_spirit_position_after_move_;
+            if is_wizard_at_position(game_state, wind_spirit_position_after_move) {
+                continue;
+            }
+            player_moves.push(PlayerMove {
+                card_index,
+                piece_index: WIND_SPIRIT_PIECE_INDEX,
+                piece_position_before_move: wind_spirit_position,
+                piece_position_after_move: wind_spirit_position_after_move,
+                wind_spirit_position_before_move: 0,
+                wind_spirit_position_after_move: 0,
+            });
+        }
+    }
+
+    return player_moves;
+}
+
+// a player without a single legal move still has to give up a card, so passing with either card
+// is the only thing left to do
+pub fn get_pass_moves(game_state: &GameState, player_id: usize) -> Vec<PlayerMove> {
+    let mut player_moves: Vec<PlayerMove> = vec![];
+    for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+        let (card_id, _) = get_player_card(game_state, player_id, card_index);
+        if card_id < 0 {
+            continue;
+        }
+        player_moves.push(PlayerMove {
+            card_index,
+            piece_index: PASS_PIECE_INDEX,
+            piece_position_before_move: 0,
+            piece_position_after_move: 0,
+            wind_spirit_position_before_move: 0,
+            wind_spirit_position_after_move: 0,
+        });
+    }
+    return player_moves;
+}
+
+pub fn get_possible_player_moves(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+) -> Vec<PlayerMove> {
+    let player_moves = get_possible_piece_moves(game_state, pre_calculated, player_id);
+    if !player_moves.is_empty() {
+        return player_moves;
+    }
+    return get_pass_moves(game_state, player_id);
+}
+
+pub fn is_pass_move(player_move: &PlayerMove) -> bool {
+    return player_move.piece_index == PASS_PIECE_INDEX;
+}
+
+pub fn apply_player_move(
+    game_state: &GameState,
+    player_id: usize,
+    player_move: &PlayerMove,
+) -> GameState {
+    let (middle_card_id, middle_card_rotation) = get_middle_card(game_state);
+    let (card_id, card_rotation) = get_player_card(game_state, player_id, player_move.card_index);
+    let mut next_game_state = *game_state;
+
+    if is_pass_move(player_move) {
+        // only the card changes hands
+    } else if player_move.piece_index == WIND_SPIRIT_PIECE_INDEX {
+        move_wind_spirit(&mut next_game_state, player_move.piece_position_after_move);
+    } else {
+        move_player_piece(
+            &mut next_game_state,
+            player_id,
+            player_move.piece_index,
+            player_move.piece_position_after_move,
+        );
+    }
+    if player_move.wind_spirit_position_after_move > 0 {
+        move_wind_spirit(
+            &mut next_game_state,
+            player_move.wind_spirit_position_after_move,
+        );
+    }
+    set_player_card(
+        &mut next_game_state,
+        player_id,
+        player_move.card_index,
+        middle_card_id,
+        middle_card_rotation,
+    );
+    set_middle_card(&mut next_game_state, card_id, -1 * card_rotation);
+    re_clculate_player_pieces_bitmap(&mut next_game_state);
+
+    return next_game_state;
+}
+
+pub fn get_move_command(
+    pre_calculated: &PreCalculated,
+    card_id: i32,
+    player_move: &PlayerMove,
+) -> String {
+    if is_pass_move(player_move) {
+        return card_id.to_string() + " " + PASS_COMMAND;
+    }
+    let piece_position_on_board_before_move = pre_calculated
+        .board_bit_values_to_cells
+        .get(&player_move.piece_position_before_move)
+        .unwrap();
+    let piece_position_on_board_after_move = pre_calculated
+        .board_bit_values_to_cells
+        .get(&player_move.piece_position_after_move)
+        .unwrap();
+    let command = card_id.to_string()
+        + " "
+        + piece_position_on_board_before_move
+        + piece_position_on_board_after_move;
+    if player_move.wind_spirit_position_after_move == 0 {
+        return command;
+    }
+    let wind_spirit_position_on_board_before_move = pre_calculated
+        .board_bit_values_to_cells
+        .get(&player_move.wind_spirit_position_before_move)
+        .unwrap();
+    let wind_spirit_position_on_board_after_move = pre_calculated
+        .board_bit_values_to_cells
+        .get(&player_move.wind_spirit_position_after_move)
+        .unwrap();
+    return command
+        + " "
+        + wind_spirit_position_on_board_before_move
+        + wind_spirit_position_on_board_after_move;
+}
+
+pub fn get_num_of_estimated_moves_for_player(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+) -> usize {
+    return get_possible_player_moves(game_state, pre_calculated, player_id).len();
+}
+
+// counts the positions reachable in exactly `depth` moves, finished games are not played on
+pub fn get_perft(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+    depth: usize,
+) -> usize {
+    if depth == 0 {
+        return 1;
+    }
+    if is_game_finished(game_state) {
+        return 0;
+    }
+    let mut num_of_leaf_nodes = 0;
+    for player_move in get_possible_player_moves(game_state, pre_calculated, player_id).iter() {
+        let next_game_state = apply_player_move(game_state, player_id, player_move);
+        num_of_leaf_nodes += get_perft(
+            &next_game_state,
+            pre_calculated,
+            get_opponent_id(player_id),
+            depth - 1,
+        );
+    }
+    return num_of_leaf_nodes;
+}
+
+pub fn create_pre_calculated() -> PreCalculated {
+    let mut pre_calculated: PreCalculated = PreCalculated {
+        valid_moves_from_position_masks: HashMap::new(),
+        board_bit_values_to_cells: HashMap::new(),
+        positions_after_card_move_map: HashMap::new(),
+        positions_after_rotated_card_move_map: HashMap::new(),
+        wind_card_ids: vec![],
+        wind_spirit_positions_after_card_move_map: HashMap::new(),
+        wind_spirit_positions_after_rotated_card_move_map: HashMap::new(),
+        wizard_race_table: HashMap::new(),
+    };
+    for valid_moves_from_position_mask in VALID_MOVES_FROM_POSITION_MASKS.iter() {
+        let [position, mask] = valid_moves_from_position_mask;
+        pre_calculated
+            .valid_moves_from_position_masks
+            .insert(*position, *mask);
+    }
+    for board_bit_value_to_cell in BOARD_BIT_VALUES_TO_CELLS.iter() {
+        let (position, cell) = board_bit_value_to_cell;
+        pre_calculated
+            .board_bit_values_to_cells
+            .insert(*position, cell.to_string());
+    }
+    return pre_calculated;
+}
+
+pub fn get_card_moves_after_rotation(
+    card_moves: &CardMoves,
+    piece_position_before_move: i32,
+    piece_valid_move_mask: i32,
+    card_rotation: i32,
+) -> Vec<i32> {
+    let mut piece_positions_after_move: Vec<i32> = Vec::new();
+    for card_move in card_moves.iter().take(NUM_OF_MOVES_PER_CARD) {
+        let shift_by = card_rotation * card_move;
+        if shift_by == 0 {
+            continue;
+        }
+        let piece_position_after_move =
+            shift_position(piece_position_before_move, shift_by) & piece_valid_move_mask;
+        if piece_position_after_move == 0 {
+            continue;
+        }
+        piece_positions_after_move.push(piece_position_after_move);
+    }
+    return piece_positions_after_move;
+}
+
+pub fn get_positions_after_card_moves_map(
+    pre_calculated: &PreCalculated,
+    card_moves_map: &CardMovesMap,
+    card_rotation: i32,
+) -> HashMap<i32, HashMap<i32, Vec<i32>>> {
+    let mut positions_after_card_moves_map = HashMap::new();
+    for y in 0..NUM_OF_TABLE_COLS {
+        for x in 0..NUM_OF_TABLE_ROWS {
+            let piece_position_before_move =
+                shift_position(1, coordinates_to_bitwise_shift(x as i32, y as i32));
+            let piece_valid_move_mask = *pre_calculated
+                .valid_moves_from_position_masks
+                .get(&piece_position_before_move)
+                .unwrap();
+            let mut card_moves_map_for_position = HashMap::new();
+            for (card_id, card_moves) in card_moves_map.iter() {
+                card_moves_map_for_position.insert(
+                    *card_id,
+                    get_card_moves_after_rotation(
+                        card_moves,
+                        piece_position_before_move,
+                        piece_valid_move_mask,
+                        card_rotation,
+                    ),
+                );
+            }
+            positions_after_card_moves_map
+                .insert(piece_position_before_move, card_moves_map_for_position);
+        }
+    }
+    return positions_after_card_moves_map;
+}
+
+pub fn pre_calculate_card_moves(
+    pre_calculated: &mut PreCalculated,
+    card_moves_map: &CardMovesMap,
+    wind_spirit_card_moves_map: &CardMovesMap,
+) {
+    pre_calculated.positions_after_card_move_map =
+        get_positions_after_card_moves_map(pre_calculated, card_moves_map, DEFAULT_CARD_ROTATION);
+    pre_calculated.positions_after_rotated_card_move_map = get_positions_after_card_moves_map(
+        pre_calculated,
+        card_moves_map,
+        -1 * DEFAULT_CARD_ROTATION,
+    );
+    pre_calculated.wind_card_ids = wind_spirit_card_moves_map.keys().copied().collect();
+    pre_calculated.wind_spirit_positions_after_card_move_map = get_positions_after_card_moves_map(
+        pre_calculated,
+        wind_spirit_card_moves_map,
+        DEFAULT_CARD_ROTATION,
+    );
+    pre_calculated.wind_spirit_positions_after_rotated_card_move_map =
+        get_positions_after_card_moves_map(
+            pre_calculated,
+            wind_spirit_card_moves_map,
+            -1 * DEFAULT_CARD_ROTATION,
+        );
+    let card_ids: Vec<i32> = card_moves_map.keys().copied().collect();
+    pre_calculated.wizard_race_table = create_wizard_race_table(pre_calculated, &card_ids);
+}
+
+pub fn create_pre_calculated_from_catalogue(card_ids: &[i32]) -> PreCalculated {
+    let mut card_moves_map: CardMovesMap = HashMap::new();
+    let mut wind_spirit_card_moves_map: CardMovesMap = HashMap::new();
+    for card_id in card_ids.iter() {
+        let (card_moves, wind_spirit_moves) = get_card_moves_from_catalogue(*card_id);
+        card_moves_map.insert(*card_id, card_moves);
+        if let Some(wind_spirit_moves) = wind_spirit_moves {
+            wind_spirit_card_moves_map.insert(*card_id, wind_spirit_moves);
+        }
+    }
+    let mut pre_calculated = create_pre_calculated();
+    pre_calculate_card_moves(
+        &mut pre_calculated,
+        &card_moves_map,
+        &wind_spirit_card_moves_map,
+    );
+    return pre_calculated;
+}
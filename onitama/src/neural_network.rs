@@ -0,0 +1,294 @@
+use crate::cards::{get_card_coordinates_from_catalogue, CardPool};
+use crate::game_state::{
+    get_middle_card, get_player_card, get_player_piece_position, get_wind_spirit_position,
+    GameState, RulesVariant, GAME_STATE_WIZARD_OFFSET, NUM_OF_CARDS_PER_PLAYER,
+    NUM_OF_PIECES_PER_PLAYER, NUM_OF_PLAYERS, WHITE_PLAYER_ID,
+};
+use crate::history::DEFAULT_DRAW_RULE;
+use crate::neural_network_weights::{
+    NEURAL_NETWORK_HIDDEN_BIASES, NEURAL_NETWORK_HIDDEN_WEIGHTS, NEURAL_NETWORK_OUTPUT_BIAS,
+    NEURAL_NETWORK_OUTPUT_WEIGHTS,
+};
+use crate::referee::{get_next_random, play_referee_game, MoveLogFormat, Random};
+use crate::search::DEFAULT_SEARCH_OPTIONS;
+
+// every feature is a 0 or 1, so the network only ever looks at the handful of active ones:
+//   0..100   a student or wizard of either player on one of the 25 cells
+//   100..125 the wind spirit on one of the 25 cells
+//   125..200 the squares white's cards, black's cards and the middle card reach, as seen from
+//            the board's point of view on a 5x5 grid of offsets centred on the piece
+pub static NUM_OF_NEURAL_NETWORK_INPUTS: usize = 200;
+
+pub static NUM_OF_NEURAL_NETWORK_HIDDEN_NODES: usize = 8;
+
+pub static NEURAL_NETWORK_WIND_SPIRIT_FEATURES_OFFSET: usize = 100;
+
+pub static NEURAL_NETWORK_CARD_FEATURES_OFFSET: usize = 125;
+
+// the output is trained towards the game result in -1..1 and scaled to the hand-written
+// evaluation's range when it is used in the search
+pub static NEURAL_NETWORK_SCORE_SCALE: f32 = 500.0;
+
+pub static NEURAL_NETWORK_LEARNING_RATE: f32 = 0.01;
+
+#[derive(Debug, Clone)]
+pub struct NeuralNetwork {
+    pub hidden_weights: Vec<f32>,
+    pub hidden_biases: Vec<f32>,
+    pub output_weights: Vec<f32>,
+    pub output_bias: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrainingSample {
+    pub features: Vec<usize>,
+    pub result: f32,
+}
+
+fn push_card_features(features: &mut Vec<usize>, holder_index: usize, card: (i32, i32)) {
+    let (card_id, card_rotation) = card;
+    let (card_coordinates, _) = match get_card_coordinates_from_catalogue(card_id) {
+        Some(card_coordinates) => card_coordinates,
+        None => return,
+    };
+    for [dx, dy] in card_coordinates.iter() {
+        if *dx == 0 && *dy == 0 {
+            continue;
+        }
+        let offset_index = ((card_rotation * dy + 2) * 5 + (card_rotation * dx + 2)) as usize;
+        let feature = NEURAL_NETWORK_CARD_FEATURES_OFFSET + holder_index * 25 + offset_index;
+        if !features.contains(&feature) {
+            features.push(feature);
+        }
+    }
+}
+
+pub fn get_neural_network_features(game_state: &GameState) -> Vec<usize> {
+    let mut features: Vec<usize> = vec![];
+    for player_id in 0..NUM_OF_PLAYERS {
+        for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+            let piece_position = get_player_piece_position(game_state, player_id, piece_index);
+            if piece_position == 0 {
+                continue;
+            }
+            let piece_type_index =
+                player_id * 2 + (piece_index == GAME_STATE_WIZARD_OFFSET) as usize;
+            features.push(piece_type_index * 25 + piece_position.trailing_zeros() as usize);
+        }
+    }
+    let wind_spirit_position = get_wind_spirit_position(game_state);
+    if wind_spirit_position > 0 {
+        features.push(
+            NEURAL_NETWORK_WIND_SPIRIT_FEATURES_OFFSET
+                + wind_spirit_position.trailing_zeros() as usize,
+        );
+    }
+    for player_id in 0..NUM_OF_PLAYERS {
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            push_card_features(
+                &mut features,
+                player_id,
+                get_player_card(game_state, player_id, card_index),
+            );
+        }
+    }
+    push_card_features(&mut features, NUM_OF_PLAYERS, get_middle_card(game_state));
+    return features;
+}
+
+fn get_hidden_activations(
+    hidden_weights: &[f32],
+    hidden_biases: &[f32],
+    features: &[usize],
+) -> Vec<f32> {
+    let mut hidden_activations = hidden_biases.to_vec();
+    for feature in features.iter() {
+        let weights_offset = feature * NUM_OF_NEURAL_NETWORK_HIDDEN_NODES;
+        for (hidden_index, hidden_activation) in hidden_activations.iter_mut().enumerate() {
+            *hidden_activation += hidden_weights[weights_offset + hidden_index];
+        }
+    }
+    for hidden_activation in hidden_activations.iter_mut() {
+        *hidden_activation = hidden_activation.max(0.0);
+    }
+    return hidden_activations;
+}
+
+fn get_output(output_weights: &[f32], output_bias: f32, hidden_activations: &[f32]) -> f32 {
+    let mut output = output_bias;
+    for (output_weight, hidden_activation) in output_weights.iter().zip(hidden_activations.iter()) {
+        output += output_weight * hidden_activation;
+    }
+    return output;
+}
+
+// scored from white's point of view like the hand-written evaluation, using the weights baked
+// into the binary
+pub fn get_neural_network_score(game_state: &GameState) -> i32 {
+    let features = get_neural_network_features(game_state);
+    let hidden_activations = get_hidden_activations(
+        &NEURAL_NETWORK_HIDDEN_WEIGHTS,
+        &NEURAL_NETWORK_HIDDEN_BIASES,
+        &features,
+    );
+    let output = get_output(
+        &NEURAL_NETWORK_OUTPUT_WEIGHTS,
+        NEURAL_NETWORK_OUTPUT_BIAS,
+        &hidden_activations,
+    );
+    return (output.tanh() * NEURAL_NETWORK_SCORE_SCALE) as i32;
+}
+
+// every position of a self-play game is labelled with how the game ended for white
+pub fn collect_training_samples(
+    random: &mut Random,
+    num_of_games: usize,
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+) -> Vec<TrainingSample> {
+    let mut training_samples: Vec<TrainingSample> = vec![];
+    for _ in 0..num_of_games {
+        let mut game_features: Vec<Vec<usize>> = vec![];
+        let (winner_id, _, _) = play_referee_game(
+            random,
+            DEFAULT_DRAW_RULE,
+            rules_variant,
+            card_pools,
+            [DEFAULT_SEARCH_OPTIONS, DEFAULT_SEARCH_OPTIONS],
+            MoveLogFormat::Silent,
+            &mut |game_state, _, _| game_features.push(get_neural_network_features(game_state)),
+        );
+        let result = match winner_id {
+            Some(winner_id) if winner_id == WHITE_PLAYER_ID => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        };
+        training_samples.extend(
+            game_features
+                .into_iter()
+                .map(|features| TrainingSample { features, result }),
+        );
+    }
+    return training_samples;
+}
+
+fn get_random_weight(random: &mut Random, scale: f32) -> f32 {
+    let unit = (get_next_random(random) % 1_000_000) as f32 / 1_000_000.0;
+    return (unit * 2.0 - 1.0) * scale;
+}
+
+pub fn create_neural_network(random: &mut Random) -> NeuralNetwork {
+    let hidden_scale = 1.0 / (NUM_OF_NEURAL_NETWORK_INPUTS as f32).sqrt();
+    let output_scale = 1.0 / (NUM_OF_NEURAL_NETWORK_HIDDEN_NODES as f32).sqrt();
+    return NeuralNetwork {
+        hidden_weights: (0..NUM_OF_NEURAL_NETWORK_INPUTS * NUM_OF_NEURAL_NETWORK_HIDDEN_NODES)
+            .map(|_| get_random_weight(random, hidden_scale))
+            .collect(),
+        hidden_biases: vec![0.0; NUM_OF_NEURAL_NETWORK_HIDDEN_NODES],
+        output_weights: (0..NUM_OF_NEURAL_NETWORK_HIDDEN_NODES)
+            .map(|_| get_random_weight(random, output_scale))
+            .collect(),
+        output_bias: 0.0,
+    };
+}
+
+// one step of stochastic gradient descent on the squared error of tanh(output), returns the error
+pub fn train_neural_network_on_sample(
+    neural_network: &mut NeuralNetwork,
+    training_sample: &TrainingSample,
+) -> f32 {
+    let hidden_activations = get_hidden_activations(
+        &neural_network.hidden_weights,
+        &neural_network.hidden_biases,
+        &training_sample.features,
+    );
+    let prediction = get_output(
+        &neural_network.output_weights,
+        neural_network.output_bias,
+        &hidden_activations,
+    )
+    .tanh();
+    let error = prediction - training_sample.result;
+    let output_gradient = 2.0 * error * (1.0 - prediction * prediction);
+
+    for (hidden_index, hidden_activation) in hidden_activations.iter().enumerate() {
+        if *hidden_activation <= 0.0 {
+            continue;
+        }
+        let hidden_gradient = output_gradient * neural_network.output_weights[hidden_index];
+        for feature in training_sample.features.iter() {
+            neural_network.hidden_weights
+                [feature * NUM_OF_NEURAL_NETWORK_HIDDEN_NODES + hidden_index] -=
+                NEURAL_NETWORK_LEARNING_RATE * hidden_gradient;
+        }
+        neural_network.hidden_biases[hidden_index] -=
+            NEURAL_NETWORK_LEARNING_RATE * hidden_gradient;
+    }
+    for (output_weight, hidden_activation) in neural_network
+        .output_weights
+        .iter_mut()
+        .zip(hidden_activations.iter())
+    {
+        *output_weight -= NEURAL_NETWORK_LEARNING_RATE * output_gradient * hidden_activation;
+    }
+    neural_network.output_bias -= NEURAL_NETWORK_LEARNING_RATE * output_gradient;
+
+    return error * error;
+}
+
+// shuffles the samples every epoch and returns the mean squared error of each epoch
+pub fn train_neural_network(
+    neural_network: &mut NeuralNetwork,
+    training_samples: &mut [TrainingSample],
+    num_of_epochs: usize,
+    random: &mut Random,
+) -> Vec<f32> {
+    let mut mean_squared_errors: Vec<f32> = vec![];
+    for _ in 0..num_of_epochs {
+        for i in (1..training_samples.len()).rev() {
+            let j = (get_next_random(random) % (i as u64 + 1)) as usize;
+            training_samples.swap(i, j);
+        }
+        let mut sum_of_squared_errors = 0.0;
+        for training_sample in training_samples.iter() {
+            sum_of_squared_errors +=
+                train_neural_network_on_sample(neural_network, training_sample);
+        }
+        mean_squared_errors.push(sum_of_squared_errors / training_samples.len().max(1) as f32);
+    }
+    return mean_squared_errors;
+}
+
+fn get_rust_array_source(name: &str, values: &[f32]) -> String {
+    let mut source = format!(
+        "#[rustfmt::skip]\npub static {}: [f32; {}] = [\n",
+        name,
+        values.len()
+    );
+    for chunk in values.chunks(NUM_OF_NEURAL_NETWORK_HIDDEN_NODES) {
+        let line: Vec<String> = chunk.iter().map(|v| format!("{:.5}", v)).collect();
+        source += &format!("    {},\n", line.join(", "));
+    }
+    source += "];\n";
+    return source;
+}
+
+// the weights end up as plain statics so the single file submission needs nothing at runtime
+pub fn get_neural_network_rust_source(neural_network: &NeuralNetwork) -> String {
+    return format!(
+        "// generated by `onitama train`, do not edit by hand\n#![allow(clippy::approx_constant)]\n\n{}\n{}\n{}\npub static NEURAL_NETWORK_OUTPUT_BIAS: f32 = {:.5};\n",
+        get_rust_array_source(
+            "NEURAL_NETWORK_HIDDEN_WEIGHTS",
+            &neural_network.hidden_weights
+        ),
+        get_rust_array_source(
+            "NEURAL_NETWORK_HIDDEN_BIASES",
+            &neural_network.hidden_biases
+        ),
+        get_rust_array_source(
+            "NEURAL_NETWORK_OUTPUT_WEIGHTS",
+            &neural_network.output_weights
+        ),
+        neural_network.output_bias
+    );
+}
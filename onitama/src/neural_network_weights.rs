@@ -0,0 +1,218 @@
+// generated by `onitama train`, do not edit by hand
+#![allow(clippy::approx_constant)]
+
+#[rustfmt::skip]
+pub static NEURAL_NETWORK_HIDDEN_WEIGHTS: [f32; 1600] = [
+    -0.31006, -0.37645, 0.74410, 0.48193, -1.09686, -0.83308, 0.35705, -0.91226,
+    0.44071, -0.73963, 0.65679, 0.02947, -0.21915, -0.43877, 1.15134, -0.50119,
+    1.36389, 1.26204, 0.62471, -1.38459, -0.06630, 1.46138, -0.25586, -1.99570,
+    -0.72067, -0.60464, -0.33978, -0.66409, 0.20426, -0.04537, -1.23020, -0.86626,
+    -0.15862, -0.50078, -1.33496, -0.17452, -0.06468, -0.30044, 0.09067, -0.76542,
+    0.83157, -0.85463, 0.23157, -0.00057, 0.30719, -0.08186, 0.60255, -0.99433,
+    0.80118, -0.59101, 0.23650, -0.70091, -0.42127, -0.04689, 0.60695, -1.40721,
+    1.19846, -0.51748, 0.10454, -0.27318, 0.12849, 0.21560, 0.87665, -1.76656,
+    0.56235, 1.07009, 0.25785, -0.65569, 0.23911, -0.17185, 0.10069, -2.70729,
+    -0.21776, -0.47337, -0.26026, -0.04693, -0.38492, -1.31447, -0.38293, -1.14964,
+    0.72319, 0.11991, -0.15085, -1.06784, 1.41315, 0.94331, 1.70222, -0.25142,
+    1.60326, -0.09551, -0.17066, -0.61965, 0.02924, 0.34476, 0.86237, -1.12593,
+    1.26132, 0.09708, 0.83828, -1.28310, 0.83697, -0.02539, 0.02435, -1.03859,
+    1.12111, -0.63584, 0.40859, -0.91735, 0.09845, -0.38521, -0.69084, -1.06172,
+    1.04371, -0.29224, -0.18160, -1.28349, 1.39713, -0.55926, 1.14246, -0.61776,
+    0.70542, 0.07161, 0.32065, -0.32717, 1.32980, -0.13232, 0.16606, -0.61211,
+    0.82935, -0.04516, 0.38279, 1.54317, 1.20961, -0.42789, -0.10648, -0.74779,
+    0.32534, -0.35165, 0.38888, -1.01902, 0.29999, -0.50550, 0.38888, -0.85627,
+    0.48071, -0.52409, 1.03841, -1.39760, 0.73412, -0.66227, 0.99166, -1.05681,
+    1.29412, 0.73826, 0.69703, 0.34855, -0.17446, -0.01187, -0.07534, -1.40116,
+    2.19523, -0.13906, 0.01165, -0.51949, 0.49795, -0.06436, 0.08995, -1.44649,
+    1.29234, 0.27597, 0.16203, -0.55520, 1.06866, -0.29970, -0.09137, -1.37635,
+    -0.07853, -0.40839, 0.90010, -0.80531, 1.09135, -0.36110, 0.24928, -0.27758,
+    1.15546, -0.10748, 0.06165, -1.18287, -0.20269, 0.19390, 0.05084, -0.97390,
+    1.07687, 0.05742, -0.03398, -0.05543, -0.09812, 0.27979, -0.04576, -0.14200,
+    -1.27829, 1.93048, -1.30133, 0.42140, -1.25004, -0.97201, -0.45863, 1.37291,
+    -1.81357, 0.16348, -0.03472, 1.79523, 0.70124, 0.93660, 2.15941, 0.74632,
+    -1.49370, -0.48972, -0.18402, -0.66838, -0.22128, -0.26202, -0.70393, 0.87297,
+    -1.98870, -0.61670, -0.69776, -0.08620, -0.77719, 0.01033, 0.36580, 2.04598,
+    -1.37046, 0.15831, -0.86580, 0.96039, -0.84381, -0.36907, 0.39666, 1.76668,
+    -0.10776, 1.37555, 0.60281, 1.76957, 0.32339, 0.37856, 0.71373, 0.36133,
+    0.10712, 0.29797, -0.28132, 0.72295, -1.09982, 1.03788, -0.62612, -0.20213,
+    -0.50945, 0.84509, 0.13977, -0.48052, -0.49819, 0.43891, -2.87713, -0.03348,
+    -0.86840, -0.43644, -0.03479, -0.76561, 0.33903, -1.08018, -0.55164, 0.45848,
+    -1.42270, -0.04103, -0.42126, 1.10075, 0.71848, -0.41981, 0.46512, 0.97781,
+    1.01245, -0.26757, -0.03193, -0.17809, 0.09997, 1.57804, -0.84335, 0.34359,
+    1.06429, -0.07605, 0.59986, 0.19508, -0.22728, 0.28857, -0.85789, -0.38813,
+    0.46889, -0.25996, 0.00630, -0.63368, 0.88306, -0.32509, 1.16495, -0.42059,
+    0.45142, -0.75018, -0.68317, -0.75253, 0.05930, 0.28026, 0.01986, -0.61153,
+    -1.21671, -0.67241, 1.02358, -0.52860, 0.06590, -0.43309, -0.04576, 0.32238,
+    -0.05018, -0.21230, 0.27504, 0.07569, 0.99570, -0.22063, -0.01274, -0.74167,
+    1.67869, 0.09807, 0.31449, -0.15989, -0.01766, 0.38682, 0.48320, -1.07788,
+    1.59553, -0.40247, 0.24360, -0.62188, 0.43369, -0.10820, -0.13359, -1.16669,
+    2.18018, -0.30758, 0.22241, -1.09934, 0.16576, -0.13044, 0.26281, -0.72490,
+    0.96281, -0.31389, 0.08853, -0.20622, 0.07463, -0.17402, -0.00830, -0.92263,
+    -0.06231, -0.08983, 0.01050, 0.06450, -0.03342, 0.11833, 0.08028, 0.11474,
+    1.51351, -0.04896, 0.07088, -0.53406, 0.35108, -0.25787, 0.07170, -0.72655,
+    -0.01342, -0.06806, -0.04213, 0.04618, 0.00907, -0.01582, 0.04063, -0.06819,
+    1.48556, -0.47418, 0.37980, -1.07422, 0.19901, -0.06735, 0.04001, -0.84381,
+    0.63920, -0.18521, 0.22583, -0.11298, 0.28436, -0.14408, -0.03195, -0.62123,
+    1.75228, -0.23878, 0.25411, -0.48519, 0.11857, -0.05778, 0.18885, -0.78539,
+    -0.19785, -0.14969, 0.88759, 0.78707, 0.16744, -0.19227, -0.06786, -0.81070,
+    -0.33542, 0.36678, -0.11669, -1.05357, -0.41409, 0.57366, -0.47290, -0.66594,
+    -1.02601, 0.06297, -0.20137, 0.77899, -0.63350, 0.80839, -0.37918, 0.15469,
+    -0.25610, 0.06856, -0.24519, 0.65505, -0.15363, 0.11228, -0.37516, 0.28995,
+    0.89427, 0.75259, -0.09688, 0.55492, -0.37511, 0.04766, -0.24387, 1.00945,
+    -0.81710, -0.40855, 0.29273, 2.14724, -1.17834, 0.40701, -0.26390, 0.67741,
+    -1.16027, 0.71097, 0.45065, 0.58914, -0.51051, 0.54264, 0.61850, 1.38693,
+    -1.51458, -0.24951, 0.01788, 1.03796, -0.59146, -0.37864, -0.38076, 1.39909,
+    -1.07072, 0.09965, -0.42806, 0.53480, -0.59778, 0.24800, -0.19087, 1.16182,
+    -0.49874, 0.34183, 0.56011, 0.74724, 0.35025, 0.73480, -0.75884, 1.22063,
+    -1.68812, 0.73167, -0.26791, 0.71379, -0.12470, 0.77470, 0.05849, 0.64902,
+    -1.27572, 0.40625, 0.13023, 1.33197, -0.87397, 0.37899, -0.31859, 1.08502,
+    -1.84313, -0.15947, -0.86431, 1.17252, -0.24347, 0.64253, 0.10861, 0.38690,
+    -1.47141, -0.22625, -1.64575, 0.98080, -0.48622, -0.41402, 0.83506, -0.63601,
+    -0.55664, -0.25803, -0.59955, -0.08223, 0.40542, 1.11722, -1.77912, 0.68201,
+    -1.63116, 0.29596, 0.12826, 0.42416, -0.21562, 0.53286, -0.70243, 0.50916,
+    -1.44802, 0.69593, 0.24760, 1.00375, -0.80288, 0.33729, 0.10581, 0.34699,
+    -1.36197, 0.65365, -0.18205, 1.13721, -0.24952, 0.27862, 0.88083, 0.57304,
+    -1.17827, -1.16356, -0.40663, 0.59058, 0.04701, 0.41133, 1.09894, -0.57784,
+    -0.28592, -0.28679, 1.14177, -0.25602, -0.22868, 1.34818, -0.81924, -0.46749,
+    -1.01123, -0.56139, -0.34669, 0.72836, -0.99082, 0.06963, -1.09754, 0.06007,
+    -1.07398, -0.03988, 0.23989, -0.11454, 0.11542, 0.20653, -0.84371, 1.12102,
+    -0.50965, -1.02442, -0.90733, 0.22432, -0.61183, -0.30199, 0.55077, -0.30272,
+    -0.82438, -0.37303, -0.31782, -0.15882, 0.02246, 0.37949, -0.22095, 0.76673,
+    -0.93877, 0.11995, -0.04054, 0.54806, 0.02551, -0.05267, -0.28378, 1.24931,
+    -1.97826, -0.25415, -0.13888, 0.48898, 0.32423, 0.01677, -0.06880, 1.43253,
+    0.04245, -0.02531, -0.06139, -0.06173, 0.02218, -0.01037, 0.00832, -0.05498,
+    -1.48601, 0.01032, -0.08508, 0.30987, -0.26397, 0.34985, -0.03525, 1.37249,
+    0.03216, -0.00412, -0.00029, 0.00271, 0.12714, -0.04267, 0.13487, -0.07069,
+    -0.84442, 0.18109, -0.27871, -0.52400, -0.38938, 0.10980, -0.11995, 1.08701,
+    -1.12621, 0.15129, -0.26036, 0.39841, -0.33315, 0.46414, 0.15563, 1.10206,
+    -1.28929, 0.19061, -0.21618, 0.10447, 0.06540, -0.07302, -0.30066, 1.44268,
+    -1.52100, 0.09483, 0.05392, 0.51129, 0.40418, -0.47576, -0.56910, 0.88160,
+    -0.19032, 0.42662, -0.21071, 0.37598, -0.73202, -0.37869, -0.13919, 1.08021,
+    0.86450, -0.09963, 0.29594, -0.09743, 0.02352, -0.08837, -0.61894, 0.62171,
+    -0.23896, -0.48612, 0.06551, -0.23296, -0.65433, 0.25981, -0.38901, -0.07320,
+    -0.50384, -0.01122, 0.61739, -0.40753, 0.00432, 0.72243, -0.02301, 0.09328,
+    -0.53347, -0.07508, 1.02742, -0.91125, -0.37695, -0.07582, 0.06787, 0.78450,
+    1.05119, 0.86032, 0.95686, 0.80210, -1.39855, -0.57554, 0.59284, -0.66382,
+    1.79225, 0.05030, 1.25303, 0.33715, 0.82130, -0.16425, 0.28762, -1.68681,
+    0.42246, 0.18102, -0.75247, -1.99254, -0.58489, -0.52581, -1.17202, -0.50737,
+    -0.79206, -0.68068, -0.85727, -0.52553, -0.42799, 0.32454, 0.66146, -1.52721,
+    0.79908, -0.56907, -0.15139, 0.15759, 0.97498, -0.36468, -1.49776, 0.22565,
+    1.97295, 0.22318, 0.33892, 0.16699, -0.30756, -1.23534, -0.02172, 0.07141,
+    2.47165, -0.20680, -0.00628, -1.63674, -0.91811, 1.40984, 1.44161, -2.60377,
+    0.46968, -0.33829, -1.19907, 0.65126, 0.70463, 0.30709, 1.13311, -1.22721,
+    0.34465, -0.55292, -1.07770, -0.69905, 0.15719, 0.20548, -0.26652, -0.53684,
+    0.52001, -0.54644, 0.41249, 0.16715, 0.56738, 1.55310, 1.11631, -1.91851,
+    1.30914, 0.50538, -0.52201, 1.24792, 2.76143, -1.04050, -0.55954, 0.03082,
+    0.04542, 0.03963, 0.01979, -0.06552, -0.02305, -0.04371, -0.05275, -0.05185,
+    0.01046, -0.00539, -0.00759, -0.00421, -0.01709, 0.00094, -0.01963, 0.00206,
+    0.03011, -0.00438, -0.01260, -0.00107, -0.01727, -0.06669, 0.03591, 0.00709,
+    0.04490, -0.03650, 0.02231, 0.03211, 0.03481, -0.06231, 0.04055, -0.01021,
+    -0.01281, -0.00975, 0.00357, -0.02455, -0.03139, -0.05201, 0.01761, -0.03183,
+    -0.05410, -0.03177, 0.00961, 0.01144, -0.05454, -0.02233, 0.05764, 0.00339,
+    -0.00518, -0.04196, 0.03769, -0.00587, 0.01046, -0.03754, 0.06749, -0.01609,
+    0.06322, 0.05353, 0.01582, 0.00900, -0.01242, -0.03188, -0.06512, -0.02815,
+    0.03370, -0.01242, -0.00880, -0.03995, -0.00423, 0.00287, -0.00021, -0.03514,
+    0.06699, 0.00158, -0.05586, 0.00466, -0.03948, 0.06175, -0.01462, 0.03424,
+    -0.03446, 0.07055, 0.06497, 0.07020, 0.02159, 0.05320, -0.05547, 0.02064,
+    0.03395, 0.04575, -0.00161, -0.03992, -0.04638, 0.02144, -0.01886, -0.01249,
+    0.02072, -0.04487, -0.05121, -0.05408, -0.00426, 0.02932, -0.02706, -0.05976,
+    -0.05139, -0.05604, 0.04379, 0.01843, 0.02699, -0.04301, 0.04318, -0.03009,
+    -0.03760, -0.01200, -0.03516, 0.03837, -0.05248, 0.01728, 0.05717, -0.00450,
+    -0.01194, 0.06638, -0.00241, 0.03337, 0.06896, 0.06399, 0.01185, 0.01026,
+    0.04285, -0.02133, 0.01060, -0.05104, 0.02204, -0.00162, -0.06356, 0.02282,
+    0.06518, -0.03424, 0.03841, -0.01929, -0.04207, -0.00604, -0.03853, -0.05695,
+    0.06632, 0.00035, -0.06533, 0.04712, -0.05475, 0.06291, 0.00056, -0.00647,
+    -0.04306, -0.04084, -0.05865, -0.01753, 0.04909, -0.01418, 0.02212, -0.06297,
+    -0.00567, -0.01201, -0.05008, -0.05016, -0.04333, -0.05104, -0.06982, 0.00087,
+    -0.03306, 0.03045, -0.01939, 0.02178, 0.05623, -0.06100, 0.04600, -0.04443,
+    -0.03215, 0.00188, -0.00626, 0.05365, -0.05016, -0.05701, 0.01009, -0.02282,
+    -0.02207, 0.06483, -0.02481, -0.06158, -0.05955, 0.01736, -0.01604, 0.03199,
+    -0.04211, -0.02358, -0.04170, 0.00745, -0.02725, 0.02621, -0.03272, 0.05349,
+    -0.00445, 0.04360, 0.05543, -0.01563, 0.01643, 0.01560, 0.03733, -0.05762,
+    -0.00514, 0.06529, -0.00460, -0.07013, -0.00044, 0.02007, -0.06903, 0.06672,
+    0.06401, -0.01319, -0.01141, 0.01186, -0.05857, -0.05698, 0.01074, 0.02529,
+    0.05342, -0.00756, -0.05617, -0.03300, 0.06311, 0.03246, -0.03656, -0.00305,
+    0.00751, 0.02158, 0.00045, -0.01329, 0.03600, -0.03188, -0.05273, -0.01892,
+    -0.05479, -0.00025, -0.01427, -0.06890, 0.06096, 0.00681, -0.03221, 0.06738,
+    0.20200, 0.03788, 1.18752, 0.35015, 0.24401, -1.89689, -0.07638, 0.75005,
+    -0.57195, 0.32276, -0.21641, 0.45738, -1.32499, -0.38257, 0.34329, -1.16691,
+    -0.40193, -0.47629, -0.11346, -0.81834, 0.15501, 0.41925, 0.44400, 0.44473,
+    -0.04825, 0.00538, -0.01804, 0.06399, 0.06202, -0.02631, 0.06366, 0.05383,
+    -0.32484, -0.09269, -0.76255, -3.06705, 2.22193, 1.91143, 0.48210, 0.65984,
+    0.30379, -0.84112, -0.32473, -0.18883, -0.77612, -0.20436, -0.63215, -0.12874,
+    -0.03707, -0.02405, 0.04281, 0.05319, -0.04365, 0.00002, 0.04851, -0.05884,
+    -0.11671, -0.94826, -0.60454, 0.03763, -2.04389, 0.92894, 0.42444, -1.04171,
+    0.00698, 1.40632, 1.80936, -0.08231, 0.43582, -0.70267, -1.80811, 1.25410,
+    -0.08686, 0.36603, 0.44135, 0.34957, 1.94812, 1.12121, -0.89504, -0.53431,
+    0.39539, -1.10852, -0.96152, -0.86298, 0.31443, 0.75307, -0.96410, 0.03212,
+    0.63266, 0.55599, -2.24877, -0.46680, 1.04284, 0.72199, -0.51664, -0.29202,
+    -0.14225, 0.68273, 1.83286, 0.41899, -0.69815, -2.01219, 0.81200, -0.46605,
+    -0.12491, 0.42396, 0.41933, 0.36898, 1.86187, 1.16403, -0.88644, -0.54927,
+    0.01915, 0.03094, 0.02174, 0.02609, 0.03810, -0.01245, 0.03692, 0.06464,
+    0.00429, -0.06513, 0.03471, 0.03761, -0.05609, -0.02119, 0.00221, -0.03734,
+    0.51888, -0.23853, -0.58527, -0.78026, 3.36458, -1.95336, -0.68322, -0.32801,
+    0.04173, 0.03548, -0.01150, -0.06561, 0.00733, -0.04938, 0.02367, -0.02480,
+    0.04379, 0.04157, -0.00989, -0.00541, -0.06745, 0.03207, -0.02681, -0.02449,
+    -0.02053, 0.05336, 0.04472, -0.05966, 0.00199, 0.00745, -0.01971, 0.02278,
+    -0.00637, -0.06237, 0.04323, -0.05452, -0.06504, -0.03379, -0.00102, 0.06573,
+    -0.52779, -0.80734, -0.76837, 1.25664, 2.09478, -1.70784, -0.88258, 0.30060,
+    0.00802, 0.03294, -0.00017, -0.02135, 0.02944, -0.04426, 0.04445, -0.02535,
+    -0.02205, -0.03606, 0.04354, -0.06909, 0.01319, -0.02285, 0.04744, -0.04340,
+    -0.50267, -0.00496, -0.15243, 0.36333, 1.44549, 0.79398, -1.69954, -0.47100,
+    -0.25007, 0.37233, 0.66630, 1.02127, -0.38004, -1.96199, -0.17189, 0.37467,
+    0.26239, -0.42606, -2.51628, -0.16610, 0.27823, 0.03610, -0.44945, 0.07896,
+    -0.38999, -0.04222, -1.41485, -0.63701, 0.61218, 0.60537, 0.22749, 0.33797,
+    -0.43436, -0.09991, -0.17636, 0.45994, 1.54513, 0.84137, -1.63264, -0.42938,
+    0.00796, 1.86846, 2.00675, -0.23682, 0.13830, -0.31143, -1.28029, 1.49094,
+    0.31194, -0.85916, -0.30172, 0.57462, -1.92287, 0.03267, 0.20338, 0.28854,
+    -0.03092, 0.00599, 0.03131, -0.05190, -0.01538, -0.02818, -0.01172, -0.01149,
+    -0.20861, -1.44160, -0.99910, -0.13082, -1.08500, -0.40849, -0.25306, -0.45900,
+    -0.03531, 0.47111, -0.65919, -2.94957, 2.25734, 0.62380, -0.01151, -0.02112,
+    0.02599, -0.00776, 0.01012, -0.06537, 0.03155, -0.05857, 0.04365, -0.03399,
+    0.05989, 0.23171, -0.06318, -0.32895, -0.21571, 0.13488, -0.39245, 0.64190,
+    -0.07484, -0.19459, -1.13475, 0.85238, -0.97348, -0.16700, 0.44353, -0.32146,
+    -0.30815, 0.23254, 0.54928, 0.52835, 0.55279, -2.42371, 0.28870, 0.29249,
+    -0.00960, -0.01894, 0.03293, -0.00662, 0.03563, -0.04960, -0.03745, 0.03063,
+    -0.03937, 0.01622, -0.06103, -0.01477, 0.04832, -0.04555, 0.00932, 0.02039,
+    -0.03949, -0.02419, 0.01394, 0.03172, 0.00008, -0.00696, 0.04528, -0.05980,
+    -0.02141, -0.02846, 0.02433, -0.00038, -0.03032, 0.04143, -0.04147, 0.07066,
+    0.04063, 0.02660, -0.05772, 0.05742, -0.04361, 0.01151, 0.00316, 0.01863,
+    -0.04084, -0.01335, 0.05312, -0.06111, -0.05269, -0.02293, 0.04798, 0.03298,
+    -0.06099, -0.00286, 0.04272, -0.01609, -0.05005, -0.02150, -0.03684, 0.05227,
+    0.06589, -0.03468, 0.06836, -0.04551, 0.04861, -0.01010, -0.02412, -0.00461,
+    -0.67683, -0.41914, -0.17914, 0.34268, -0.39378, -0.33020, -0.54556, -0.06081,
+    -0.03250, -0.06904, 0.05571, -0.01674, 0.03819, -0.06917, -0.01902, 0.03377,
+    0.00679, 0.06653, 0.04446, -0.04049, 0.05282, -0.06482, -0.06588, -0.04115,
+    -0.55576, -0.06477, 0.36030, 1.01733, 1.56202, 0.54859, -0.58383, -0.00704,
+    -0.20419, 0.37339, 1.32503, 0.73717, -0.94097, -1.52460, 0.09537, 0.69451,
+    -0.19403, -0.12765, -1.18475, 0.44202, -0.82189, 0.09952, -0.05345, 0.24231,
+    -0.22841, -0.53856, -0.73715, -0.70139, -0.40044, 0.56328, -0.04928, 0.75258,
+    -0.58204, 0.02292, 0.37568, 1.03534, 1.48300, 0.54864, -0.71615, -0.07857,
+    -0.06344, 0.72661, 0.28122, -1.60302, 0.94559, 1.11039, -0.44897, 0.78302,
+    0.22991, -0.89147, -0.09307, -0.00737, -1.75310, 0.01685, 0.01659, -0.08837,
+    -0.00275, 0.01836, 0.02593, 0.03507, -0.06631, -0.03280, 0.03543, -0.04140,
+    0.08811, -0.96648, -0.33896, -0.02152, -1.70290, 0.10793, -0.36001, 0.27445,
+    -0.06063, 0.41499, 0.50717, -1.27079, 0.68999, 0.40606, -0.50955, 1.17699,
+    -0.00786, 0.22184, 0.29814, 0.13258, 1.60886, 0.92974, -0.55129, -0.58425,
+    -0.01237, -0.04240, -0.74502, -1.09583, 0.00629, 0.65794, -0.18609, -0.12283,
+    0.31453, 0.15391, -0.89402, 0.00198, -0.77724, 0.40352, -0.03470, -0.44625,
+    0.11327, 0.17092, 0.91664, 0.22257, -1.26009, -1.42529, 0.12673, 0.29818,
+    -0.00242, 0.23665, 0.26344, 0.20039, 1.62778, 1.00815, -0.58399, -0.54971,
+    -0.00929, -0.03915, -0.02898, -0.00901, -0.03864, -0.04166, -0.04401, 0.06326,
+    -0.06168, 0.00295, -0.01636, -0.01348, 0.05747, -0.05433, 0.06865, 0.04676,
+    0.39674, -0.47957, 0.23754, 0.01263, 0.87663, -0.40491, 0.33590, -1.16229,
+    0.00387, -0.05748, -0.06148, -0.04771, -0.06226, -0.04804, 0.06286, 0.04554,
+    -0.01302, -0.06421, 0.03958, -0.06960, -0.04661, 0.05482, -0.03186, -0.04590,
+];
+
+#[rustfmt::skip]
+pub static NEURAL_NETWORK_HIDDEN_BIASES: [f32; 8] = [
+    0.84349, -0.91329, -0.51765, -0.67278, 0.66933, 0.57910, -0.73368, 0.73753,
+];
+
+#[rustfmt::skip]
+pub static NEURAL_NETWORK_OUTPUT_WEIGHTS: [f32; 8] = [
+    0.69294, -0.89828, 0.98664, -0.54002, 0.93560, -0.41404, 0.72876, -0.66315,
+];
+
+pub static NEURAL_NETWORK_OUTPUT_BIAS: f32 = 0.10140;
@@ -0,0 +1,174 @@
+use crate::evaluation::get_game_score_for_maximizing_player_with_backend;
+use crate::game_state::{
+    get_middle_card, get_opponent_id, get_player_card, get_player_piece_position,
+    get_player_pieces_bitmask, get_wind_spirit_position, is_game_finished, GameState,
+    GAME_STATE_WIZARD_OFFSET, NUM_OF_CARDS_PER_PLAYER, NUM_OF_PLAYERS,
+};
+use crate::history::GameHistory;
+use crate::move_generation::{
+    apply_player_move, get_move_command, get_possible_player_moves, PreCalculated,
+};
+use crate::search::{search_next_move_to_depth, SearchOptions};
+
+// the opponent's replies are ranked with a shallow search of their own, deep enough to see a
+// capture coming back but cheap next to the real search
+pub static OPPONENT_MODEL_SEARCH_DEPTH: usize = 2;
+
+pub static OPPONENT_MODEL_NUM_OF_TOP_CHOICES: usize = 3;
+
+pub static OPPONENT_MODEL_MIN_NUM_OF_OBSERVED_MOVES: usize = 4;
+
+// the draw penalty against an opponent that never plays our best reply, it shrinks linearly the
+// more often they do
+pub static OPPONENT_MODEL_MAX_CONTEMPT: i32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct RankedMove {
+    pub command: String,
+    pub game_state: GameState,
+    // from the point of view of the player who is modelling, the opponent wants it low
+    pub score: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ObservedMove {
+    pub command: String,
+    // how many of the opponent's moves scored strictly better for them
+    pub rank: usize,
+    pub num_of_moves: usize,
+}
+
+#[derive(Debug)]
+pub struct OpponentModel {
+    pub observed_moves: Vec<ObservedMove>,
+    pub num_of_best_choice_matches: usize,
+    pub num_of_top_choice_matches: usize,
+}
+
+pub fn create_opponent_model() -> OpponentModel {
+    return OpponentModel {
+        observed_moves: vec![],
+        num_of_best_choice_matches: 0,
+        num_of_top_choice_matches: 0,
+    };
+}
+
+fn get_sorted_card_ids(game_state: &GameState, player_id: usize) -> Vec<i32> {
+    let mut card_ids: Vec<i32> = (0..NUM_OF_CARDS_PER_PLAYER)
+        .map(|card_index| get_player_card(game_state, player_id, card_index).0)
+        .collect();
+    card_ids.sort();
+    return card_ids;
+}
+
+// the referee only tells where the pieces are and who holds which card, so positions are
+// compared on that and not on the order or rotation the cards happen to be stored in
+pub fn is_same_observed_position(game_state: &GameState, other_game_state: &GameState) -> bool {
+    for player_id in 0..NUM_OF_PLAYERS {
+        if get_player_pieces_bitmask(game_state, player_id)
+            != get_player_pieces_bitmask(other_game_state, player_id)
+            || get_player_piece_position(game_state, player_id, GAME_STATE_WIZARD_OFFSET)
+                != get_player_piece_position(other_game_state, player_id, GAME_STATE_WIZARD_OFFSET)
+            || get_sorted_card_ids(game_state, player_id)
+                != get_sorted_card_ids(other_game_state, player_id)
+        {
+            return false;
+        }
+    }
+    return get_middle_card(game_state).0 == get_middle_card(other_game_state).0
+        && get_wind_spirit_position(game_state) == get_wind_spirit_position(other_game_state);
+}
+
+// every move the opponent had, best for them first
+pub fn get_ranked_opponent_moves(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &GameHistory,
+    search_options: SearchOptions,
+) -> Vec<RankedMove> {
+    let opponent_id = get_opponent_id(player_id);
+    let mut ranked_moves: Vec<RankedMove> = vec![];
+    for player_move in get_possible_player_moves(game_state, pre_calculated, opponent_id).iter() {
+        let (card_id, _) = get_player_card(game_state, opponent_id, player_move.card_index);
+        let next_game_state = apply_player_move(game_state, opponent_id, player_move);
+        let score = if is_game_finished(&next_game_state) {
+            get_game_score_for_maximizing_player_with_backend(
+                &next_game_state,
+                player_id,
+                search_options.evaluation_backend,
+                &search_options.engine_parameters,
+            )
+        } else {
+            search_next_move_to_depth(
+                &next_game_state,
+                player_id,
+                pre_calculated,
+                &mut game_history.clone(),
+                search_options,
+                OPPONENT_MODEL_SEARCH_DEPTH - 1,
+            )
+            .score
+        };
+        ranked_moves.push(RankedMove {
+            command: get_move_command(pre_calculated, card_id, player_move),
+            game_state: next_game_state,
+            score,
+        });
+    }
+    ranked_moves.sort_by_key(|v| v.score);
+    return ranked_moves;
+}
+
+// infers the move from the position the opponent left behind, returns None when no legal move
+// leads there, e.g. after a turn the engine answered with a fallback
+pub fn record_opponent_move(
+    opponent_model: &mut OpponentModel,
+    ranked_moves: &[RankedMove],
+    observed_game_state: &GameState,
+) -> Option<ObservedMove> {
+    let ranked_move = ranked_moves
+        .iter()
+        .find(|v| is_same_observed_position(&v.game_state, observed_game_state))?;
+    let observed_move = ObservedMove {
+        command: ranked_move.command.clone(),
+        rank: ranked_moves
+            .iter()
+            .filter(|v| v.score < ranked_move.score)
+            .count(),
+        num_of_moves: ranked_moves.len(),
+    };
+    if observed_move.rank == 0 {
+        opponent_model.num_of_best_choice_matches += 1;
+    }
+    if observed_move.rank < OPPONENT_MODEL_NUM_OF_TOP_CHOICES {
+        opponent_model.num_of_top_choice_matches += 1;
+    }
+    opponent_model.observed_moves.push(observed_move.clone());
+    return Some(observed_move);
+}
+
+pub fn get_best_choice_match_rate(opponent_model: &OpponentModel) -> f64 {
+    if opponent_model.observed_moves.is_empty() {
+        return 1.0;
+    }
+    return opponent_model.num_of_best_choice_matches as f64
+        / opponent_model.observed_moves.len() as f64;
+}
+
+pub fn get_top_choice_match_rate(opponent_model: &OpponentModel) -> f64 {
+    if opponent_model.observed_moves.is_empty() {
+        return 1.0;
+    }
+    return opponent_model.num_of_top_choice_matches as f64
+        / opponent_model.observed_moves.len() as f64;
+}
+
+// a handful of moves says little, until then the opponent is assumed to play perfectly
+pub fn get_opponent_contempt(opponent_model: &OpponentModel) -> i32 {
+    if opponent_model.observed_moves.len() < OPPONENT_MODEL_MIN_NUM_OF_OBSERVED_MOVES {
+        return 0;
+    }
+    let miss_rate = 1.0 - get_best_choice_match_rate(opponent_model);
+    return (miss_rate * OPPONENT_MODEL_MAX_CONTEMPT as f64) as i32;
+}
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::time::Instant;
+
+use crate::cards::{CardMoves, CardMovesMap};
+use crate::game_state::{
+    coordinates_to_bitwise_shift, re_clculate_player_pieces_bitmap, set_middle_card,
+    set_pieces_from_board_rows, set_player_card, GameState, RulesVariant, BLACK_PLAYER_ID,
+    DEFAULT_CARD_ROTATION, NUM_OF_CARDS, NUM_OF_CARDS_PER_PLAYER, NUM_OF_STUDENTS_PER_PLAYER,
+    NUM_OF_TABLE_COLS, NUM_OF_TABLE_ROWS, WHITE_PLAYER_ID,
+};
+
+static NUM_OF_CARD_VALUES: usize = 10;
+
+static NUM_OF_WIND_CARD_VALUES: usize = 18;
+
+// no turn lists more actions than either card moving one of six pieces, the wind spirit included,
+// followed by one of the wind spirit's four moves, 2 * 6 * 4 * 4, a larger count is not trusted
+// with the lines that follow it, they may well be the next turn's
+static MAX_ACTION_COUNT: i32 = 192;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "line {}: expected {}, found {:?}",
+            self.line_number, self.expected, self.found
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardOwner {
+    Player(usize),
+    Middle,
+}
+
+#[derive(Debug, Clone)]
+pub struct TurnCard {
+    pub owner: CardOwner,
+    pub card_id: i32,
+    pub card_moves: CardMoves,
+    pub wind_spirit_moves: Option<CardMoves>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TurnAction {
+    pub card_id: i32,
+    pub command: String,
+}
+
+// received_at is when the turn's first line arrived, the referee's clock for the turn is already
+// running by then
+#[derive(Debug, Clone)]
+pub struct TurnInput {
+    pub received_at: Instant,
+    pub board_rows: Vec<String>,
+    pub cards: Vec<TurnCard>,
+    pub actions: Vec<TurnAction>,
+}
+
+#[derive(Debug)]
+pub struct InputReader<R: BufRead> {
+    pub reader: R,
+    pub line_number: usize,
+}
+
+pub fn create_input_reader<R: BufRead>(reader: R) -> InputReader<R> {
+    return InputReader {
+        reader,
+        line_number: 0,
+    };
+}
+
+fn create_parse_error(line_number: usize, expected: &str, found: &str) -> ParseError {
+    return ParseError {
+        line_number,
+        expected: expected.to_string(),
+        found: found.to_string(),
+    };
+}
+
+// none once the input is closed, which is how the game ends
+pub fn read_input_line<R: BufRead>(input_reader: &mut InputReader<R>) -> Option<(usize, String)> {
+    let mut input_line = String::new();
+    match input_reader.reader.read_line(&mut input_line) {
+        Ok(0) | Err(_) => return None,
+        Ok(_) => {}
+    }
+    input_reader.line_number += 1;
+    return Some((
+        input_reader.line_number,
+        input_line.trim_end_matches(&['\n', '\r'][..]).to_string(),
+    ));
+}
+
+fn parse_number(
+    line_number: usize,
+    value: Option<&str>,
+    expected: &str,
+    parse_errors: &mut Vec<ParseError>,
+) -> i32 {
+    let value = value.unwrap_or("");
+    return match value.trim().parse::<i32>() {
+        Ok(number) => number,
+        Err(_) => {
+            parse_errors.push(create_parse_error(line_number, expected, value));
+            0
+        }
+    };
+}
+
+pub fn read_player_id<R: BufRead>(input_reader: &mut InputReader<R>) -> Result<usize, ParseError> {
+    let (line_number, input_line) = read_input_line(input_reader)
+        .ok_or_else(|| create_parse_error(input_reader.line_number + 1, "player id", ""))?;
+    return match input_line.trim() {
+        "0" => Ok(WHITE_PLAYER_ID),
+        "1" => Ok(BLACK_PLAYER_ID),
+        _ => Err(create_parse_error(
+            line_number,
+            "player id 0 or 1",
+            &input_line,
+        )),
+    };
+}
+
+fn parse_board_row(
+    line_number: usize,
+    input_line: &str,
+    rules_variant: RulesVariant,
+    parse_errors: &mut Vec<ParseError>,
+) -> String {
+    let valid_cells = if rules_variant == RulesVariant::WayOfTheWind {
+        "wWbBS-"
+    } else {
+        "wWbB-"
+    };
+    let mut board_row = String::new();
+    for (cell_index, cell) in input_line.chars().enumerate() {
+        if cell_index >= NUM_OF_TABLE_ROWS {
+            break;
+        }
+        if valid_cells.contains(cell) {
+            board_row.push(cell);
+            continue;
+        }
+        parse_errors.push(create_parse_error(
+            line_number,
+            &format!("cell {} to be one of {}", cell_index + 1, valid_cells),
+            &cell.to_string(),
+        ));
+        board_row.push('-');
+    }
+    if input_line.chars().count() != NUM_OF_TABLE_ROWS {
+        parse_errors.push(create_parse_error(
+            line_number,
+            &format!("a board row of {} cells", NUM_OF_TABLE_ROWS),
+            input_line,
+        ));
+    }
+    while board_row.len() < NUM_OF_TABLE_ROWS {
+        board_row.push('-');
+    }
+    return board_row;
+}
+
+// a player has four student slots and one wizard slot, the pieces counted past them are reported
+// and left off the board before anything is written to a game state
+fn remove_excess_pieces(
+    line_number: usize,
+    board_row: &str,
+    num_of_pieces: &mut HashMap<char, usize>,
+    parse_errors: &mut Vec<ParseError>,
+) -> String {
+    let mut kept_board_row = String::new();
+    for cell in board_row.chars() {
+        let max_num_of_pieces = match cell {
+            'w' | 'b' => NUM_OF_STUDENTS_PER_PLAYER,
+            'W' | 'B' => 1,
+            _ => {
+                kept_board_row.push(cell);
+                continue;
+            }
+        };
+        let num_of_pieces_of_kind = num_of_pieces.entry(cell).or_insert(0);
+        *num_of_pieces_of_kind += 1;
+        if *num_of_pieces_of_kind <= max_num_of_pieces {
+            kept_board_row.push(cell);
+            continue;
+        }
+        parse_errors.push(create_parse_error(
+            line_number,
+            &format!("at most {} {} on the board", max_num_of_pieces, cell),
+            &format!("{} number {}", cell, num_of_pieces_of_kind),
+        ));
+        kept_board_row.push('-');
+    }
+    return kept_board_row;
+}
+
+fn parse_card_moves(
+    line_number: usize,
+    inputs: &[&str],
+    offset: usize,
+    parse_errors: &mut Vec<ParseError>,
+) -> CardMoves {
+    let mut card_moves: CardMoves = [0; 4];
+    for (move_index, card_move) in card_moves.iter_mut().enumerate() {
+        let dx = parse_number(
+            line_number,
+            inputs.get(offset + move_index * 2).copied(),
+            &format!("dx{}", move_index + 1),
+            parse_errors,
+        );
+        let dy = parse_number(
+            line_number,
+            inputs.get(offset + move_index * 2 + 1).copied(),
+            &format!("dy{}", move_index + 1),
+            parse_errors,
+        );
+        *card_move = coordinates_to_bitwise_shift(dx, dy);
+    }
+    return card_moves;
+}
+
+fn parse_card(
+    line_number: usize,
+    input_line: &str,
+    rules_variant: RulesVariant,
+    parse_errors: &mut Vec<ParseError>,
+) -> Option<TurnCard> {
+    let inputs = input_line.split_whitespace().collect::<Vec<_>>();
+    if inputs.len() < NUM_OF_CARD_VALUES {
+        parse_errors.push(create_parse_error(
+            line_number,
+            &format!("{} card values", NUM_OF_CARD_VALUES),
+            input_line,
+        ));
+    }
+    let owner = match inputs.first().copied() {
+        Some("0") => CardOwner::Player(WHITE_PLAYER_ID),
+        Some("1") => CardOwner::Player(BLACK_PLAYER_ID),
+        Some("-1") => CardOwner::Middle,
+        found => {
+            parse_errors.push(create_parse_error(
+                line_number,
+                "card owner 0, 1 or -1",
+                found.unwrap_or(""),
+            ));
+            return None;
+        }
+    };
+    let card_id = parse_number(line_number, inputs.get(1).copied(), "card id", parse_errors);
+    let card_moves = parse_card_moves(line_number, &inputs, 2, parse_errors);
+
+    // wind cards carry four more dx dy pairs describing the wind spirit's moves
+    let mut wind_spirit_moves = None;
+    if rules_variant == RulesVariant::WayOfTheWind && inputs.len() >= NUM_OF_WIND_CARD_VALUES {
+        wind_spirit_moves = Some(parse_card_moves(
+            line_number,
+            &inputs,
+            NUM_OF_CARD_VALUES,
+            parse_errors,
+        ));
+    }
+
+    return Some(TurnCard {
+        owner,
+        card_id,
+        card_moves,
+        wind_spirit_moves,
+    });
+}
+
+fn parse_action(
+    line_number: usize,
+    input_line: &str,
+    parse_errors: &mut Vec<ParseError>,
+) -> Option<TurnAction> {
+    let inputs = input_line.split_whitespace().collect::<Vec<_>>();
+    if inputs.len() < 2 {
+        parse_errors.push(create_parse_error(
+            line_number,
+            "an action as card id and move",
+            input_line,
+        ));
+        return None;
+    }
+    let num_of_parse_errors = parse_errors.len();
+    let card_id = parse_number(
+        line_number,
+        inputs.first().copied(),
+        "card id",
+        parse_errors,
+    );
+    if parse_errors.len() > num_of_parse_errors {
+        return None;
+    }
+    return Some(TurnAction {
+        card_id,
+        command: inputs.join(" "),
+    });
+}
+
+fn get_num_of_cards_for_owner(cards: &[TurnCard], owner: CardOwner) -> usize {
+    return cards.iter().filter(|v| v.owner == owner).count();
+}
+
+// without every card in place there is nothing to search, the move tables would have holes
+pub fn is_every_card_dealt(cards: &[TurnCard]) -> bool {
+    return get_num_of_cards_for_owner(cards, CardOwner::Player(WHITE_PLAYER_ID))
+        == NUM_OF_CARDS_PER_PLAYER
+        && get_num_of_cards_for_owner(cards, CardOwner::Player(BLACK_PLAYER_ID))
+            == NUM_OF_CARDS_PER_PLAYER
+        && get_num_of_cards_for_owner(cards, CardOwner::Middle) == 1;
+}
+
+// reads every line of a turn even when some of them are malformed, the best effort turn is
+// returned together with everything that went wrong so the caller can decide whether to trust it,
+// none means the input was closed
+pub fn read_turn<R: BufRead>(
+    input_reader: &mut InputReader<R>,
+    rules_variant: RulesVariant,
+) -> Option<(TurnInput, Vec<ParseError>)> {
+    let mut parse_errors: Vec<ParseError> = vec![];
+
+    let mut received_at: Option<Instant> = None;
+    let mut board_rows: Vec<String> = vec![];
+    let mut num_of_pieces: HashMap<char, usize> = HashMap::new();
+    for _ in 0..NUM_OF_TABLE_COLS {
+        let (line_number, input_line) = read_input_line(input_reader)?;
+        received_at.get_or_insert_with(Instant::now);
+        let board_row = parse_board_row(line_number, &input_line, rules_variant, &mut parse_errors);
+        board_rows.push(remove_excess_pieces(
+            line_number,
+            &board_row,
+            &mut num_of_pieces,
+            &mut parse_errors,
+        ));
+    }
+
+    let mut cards: Vec<TurnCard> = vec![];
+    for _ in 0..NUM_OF_CARDS {
+        let (line_number, input_line) = read_input_line(input_reader)?;
+        if let Some(card) = parse_card(line_number, &input_line, rules_variant, &mut parse_errors) {
+            cards.push(card);
+        }
+    }
+    if !is_every_card_dealt(&cards) {
+        parse_errors.push(create_parse_error(
+            input_reader.line_number,
+            "two cards per player and one middle card",
+            &format!(
+                "{} white, {} black, {} middle",
+                get_num_of_cards_for_owner(&cards, CardOwner::Player(WHITE_PLAYER_ID)),
+                get_num_of_cards_for_owner(&cards, CardOwner::Player(BLACK_PLAYER_ID)),
+                get_num_of_cards_for_owner(&cards, CardOwner::Middle)
+            ),
+        ));
+    }
+
+    let (line_number, input_line) = read_input_line(input_reader)?;
+    let action_count = parse_number(
+        line_number,
+        Some(input_line.as_str()),
+        "action count",
+        &mut parse_errors,
+    );
+    let action_count = if (0..=MAX_ACTION_COUNT).contains(&action_count) {
+        action_count
+    } else {
+        parse_errors.push(create_parse_error(
+            line_number,
+            &format!("an action count from 0 to {}", MAX_ACTION_COUNT),
+            &input_line,
+        ));
+        0
+    };
+    // the board and the cards are still usable when the input ends part way through the actions
+    let mut actions: Vec<TurnAction> = vec![];
+    for action_index in 0..action_count {
+        let (line_number, input_line) = match read_input_line(input_reader) {
+            Some(input_line) => input_line,
+            None => {
+                parse_errors.push(create_parse_error(
+                    input_reader.line_number + 1,
+                    &format!("{} actions", action_count),
+                    &format!("{} before the input ended", action_index),
+                ));
+                break;
+            }
+        };
+        if let Some(action) = parse_action(line_number, &input_line, &mut parse_errors) {
+            actions.push(action);
+        }
+    }
+
+    return Some((
+        TurnInput {
+            received_at: received_at.unwrap_or_else(Instant::now),
+            board_rows,
+            cards,
+            actions,
+        },
+        parse_errors,
+    ));
+}
+
+// cards beyond the two a player can hold are dropped, they have already been reported
+pub fn create_game_state_from_turn(
+    turn_input: &TurnInput,
+    rules_variant: RulesVariant,
+) -> (GameState, CardMovesMap, CardMovesMap) {
+    let mut game_state: GameState = [0; 23];
+    let mut card_moves_map: CardMovesMap = HashMap::new();
+    let mut wind_spirit_card_moves_map: CardMovesMap = HashMap::new();
+
+    set_pieces_from_board_rows(&mut game_state, &turn_input.board_rows, rules_variant);
+
+    let mut num_of_player_cards: [usize; 2] = [0; 2];
+    for card in turn_input.cards.iter() {
+        card_moves_map.insert(card.card_id, card.card_moves);
+        if let Some(wind_spirit_moves) = card.wind_spirit_moves {
+            wind_spirit_card_moves_map.insert(card.card_id, wind_spirit_moves);
+        }
+        match card.owner {
+            CardOwner::Player(player_id) => {
+                if num_of_player_cards[player_id] >= NUM_OF_CARDS_PER_PLAYER {
+                    continue;
+                }
+                set_player_card(
+                    &mut game_state,
+                    player_id,
+                    num_of_player_cards[player_id],
+                    card.card_id,
+                    DEFAULT_CARD_ROTATION,
+                );
+                num_of_player_cards[player_id] += 1;
+            }
+            CardOwner::Middle => {
+                set_middle_card(&mut game_state, card.card_id, DEFAULT_CARD_ROTATION);
+            }
+        }
+    }
+
+    re_clculate_player_pieces_bitmap(&mut game_state);
+
+    return (game_state, card_moves_map, wind_spirit_card_moves_map);
+}
@@ -0,0 +1,162 @@
+use std::time::Instant;
+
+use crate::cards::{get_card_pool_card_ids, CardPool, WIND_CARD_CATALOGUE};
+use crate::game_state::{
+    create_initial_game_state, get_opponent_id, get_winner_id, GameState, RulesVariant,
+    WHITE_PLAYER_ID,
+};
+use crate::history::{
+    create_game_history, get_position_key, is_draw_by_repetition, is_draw_by_turn_limit, DrawRule,
+};
+use crate::move_generation::create_pre_calculated_from_catalogue;
+use crate::search::{search_next_move, SearchOptions, SearchResult};
+use crate::stats::get_search_stats_json;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveLogFormat {
+    Silent,
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct Random {
+    pub state: u64,
+}
+
+pub fn create_random(seed: u64) -> Random {
+    return Random {
+        state: if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        },
+    };
+}
+
+pub fn get_next_random(random: &mut Random) -> u64 {
+    random.state ^= random.state << 13;
+    random.state ^= random.state >> 7;
+    random.state ^= random.state << 17;
+    return random.state;
+}
+
+pub fn get_random_card_ids(
+    random: &mut Random,
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+) -> [i32; 5] {
+    let mut card_ids: Vec<i32> = card_pools
+        .iter()
+        .flat_map(|v| get_card_pool_card_ids(*v))
+        .collect();
+    if rules_variant == RulesVariant::WayOfTheWind {
+        card_ids.extend(WIND_CARD_CATALOGUE.iter().map(|v| v.0));
+    }
+    for i in (1..card_ids.len()).rev() {
+        let j = (get_next_random(random) % (i as u64 + 1)) as usize;
+        card_ids.swap(i, j);
+    }
+    return [
+        card_ids[0],
+        card_ids[1],
+        card_ids[2],
+        card_ids[3],
+        card_ids[4],
+    ];
+}
+
+pub fn get_player_name(player_id: usize) -> &'static str {
+    if player_id == WHITE_PLAYER_ID {
+        return "white";
+    }
+    return "black";
+}
+
+pub fn play_referee_game(
+    random: &mut Random,
+    draw_rule: DrawRule,
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+    search_options: [SearchOptions; 2],
+    move_log_format: MoveLogFormat,
+    on_move_searched: &mut dyn FnMut(&GameState, usize, &SearchResult),
+) -> (Option<usize>, String, usize) {
+    let card_ids = get_random_card_ids(random, rules_variant, card_pools);
+    let pre_calculated = create_pre_calculated_from_catalogue(&card_ids);
+
+    let mut player_id = WHITE_PLAYER_ID;
+    let mut game_state = create_initial_game_state(&card_ids, player_id, rules_variant);
+    let mut game_history = create_game_history(draw_rule);
+    game_history
+        .position_keys
+        .push(get_position_key(&game_state, player_id));
+
+    loop {
+        let start = Instant::now();
+        let search_result = search_next_move(
+            &game_state,
+            player_id,
+            &pre_calculated,
+            &mut game_history,
+            search_options[player_id],
+        );
+        on_move_searched(&game_state, player_id, &search_result);
+        if search_result.command.is_empty() {
+            return (
+                Some(get_opponent_id(player_id)),
+                format!("{} has no legal move", get_player_name(player_id)),
+                game_history.num_of_turns_played,
+            );
+        }
+        match move_log_format {
+            MoveLogFormat::Silent => {}
+            MoveLogFormat::Text => eprintln!(
+                "{} {} s: {}, d: {}, n: {}",
+                get_player_name(player_id),
+                search_result.command,
+                search_result.score,
+                search_result.target_depth,
+                search_result.num_of_nodes
+            ),
+            MoveLogFormat::Json => eprintln!(
+                "{}",
+                get_search_stats_json(
+                    &search_result,
+                    game_history.num_of_turns_played,
+                    player_id,
+                    start.elapsed()
+                )
+            ),
+        }
+
+        game_state = search_result.game_state;
+        player_id = get_opponent_id(player_id);
+        game_history.num_of_turns_played += 1;
+        game_history
+            .position_keys
+            .push(get_position_key(&game_state, player_id));
+
+        if let Some(winner_id) = get_winner_id(&game_state) {
+            return (
+                Some(winner_id),
+                format!("{} wins", get_player_name(winner_id)),
+                game_history.num_of_turns_played,
+            );
+        }
+        if is_draw_by_repetition(&game_history) {
+            return (
+                None,
+                "draw by repetition".to_string(),
+                game_history.num_of_turns_played,
+            );
+        }
+        if is_draw_by_turn_limit(&game_history, 0) {
+            return (
+                None,
+                "draw by turn limit".to_string(),
+                game_history.num_of_turns_played,
+            );
+        }
+    }
+}
@@ -0,0 +1,789 @@
+use std::cmp;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::engine_parameters::{EngineParameters, DEFAULT_ENGINE_PARAMETERS};
+use crate::evaluation::{
+    get_card_flow_score, get_game_score_for_maximizing_player_with_backend, get_wizard_race_score,
+    EvaluationBackend,
+};
+use crate::game_state::{
+    get_opponent_id, get_player_card, get_player_piece_position, get_player_pieces_bitmask,
+    is_game_finished, GameState, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET, WHITE_PLAYER_ID,
+};
+use crate::history::{
+    get_num_of_position_repetitions, get_position_key, is_draw_by_turn_limit, GameHistory,
+};
+use crate::move_generation::{
+    apply_player_move, get_move_command, get_num_of_estimated_moves_for_player,
+    get_possible_player_moves, PreCalculated,
+};
+
+pub static INFINITY: i32 = 100000000;
+
+pub static DRAW_SCORE: i32 = 0;
+
+pub static ASPIRATION_WINDOW: i32 = 50;
+
+pub static LATE_MOVE_REDUCTION_MIN_DEPTH: usize = 3;
+
+pub static LATE_MOVE_REDUCTION_MIN_CHILD_INDEX: usize = 3;
+
+pub static FUTILITY_PRUNING_MAX_DEPTH: usize = 3;
+
+pub static FUTILITY_MARGIN: i32 = 100;
+
+// codingame gives the first turn a longer budget, an answer that comes later than the budget
+// loses the game
+pub static FIRST_TURN_TIME_BUDGET_MS: u64 = 1000;
+
+pub static TURN_TIME_BUDGET_MS: u64 = 50;
+
+// the search stops this long before the budget runs out to leave room for writing the answer
+pub static TURN_TIME_SAFETY_MARGIN_MS: u64 = 5;
+
+// with less time than this left only a depth 1 search is run, it is still better than any move
+// picked without looking
+pub static EMERGENCY_SEARCH_MIN_TIME_LEFT_MS: u64 = 10;
+
+// the fewest estimated moves in total for searching 3, 4 and 5 plies deep, 6 otherwise
+pub static TARGET_DEPTH_MIN_NUM_OF_MOVES: [i32; 3] = [39, 19, 11];
+
+pub static MAX_SEARCH_DEPTH: usize = 64;
+
+// the searched tree stays in memory until the search ends, at a couple of hundred bytes per node
+// this keeps an unbounded search below half a gigabyte
+pub static MAX_NUM_OF_TREE_NODES: usize = 1_500_000;
+
+// checking the clock on every node is measurable, once every few hundred visits is plenty
+pub static NUM_OF_VISITS_BETWEEN_STOP_CHECKS: usize = 256;
+
+// the search keeps its tree between iterations instead of using a transposition table, a tree hit
+// is a node whose children were already generated by an earlier iteration
+#[derive(Debug, Clone)]
+pub struct SearchStats {
+    pub num_of_tree_probes: usize,
+    pub num_of_tree_hits: usize,
+    pub num_of_cutoffs_per_ply: Vec<usize>,
+}
+
+pub fn create_search_stats() -> SearchStats {
+    return SearchStats {
+        num_of_tree_probes: 0,
+        num_of_tree_hits: 0,
+        num_of_cutoffs_per_ply: vec![],
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub command: String,
+    pub score: i32,
+    pub game_state: GameState,
+    pub target_depth: usize,
+    pub num_of_nodes: usize,
+    pub search_stats: SearchStats,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SearchOptions {
+    pub is_late_move_reduction_enabled: bool,
+    pub is_futility_pruning_enabled: bool,
+    pub evaluation_backend: EvaluationBackend,
+    pub is_card_flow_evaluation_enabled: bool,
+    pub is_wizard_race_evaluation_enabled: bool,
+    pub engine_parameters: EngineParameters,
+    // how much worse than a draw score a draw is for the root player, worth raising against an
+    // opponent that is not expected to hold on
+    pub contempt: i32,
+}
+
+pub static DEFAULT_SEARCH_OPTIONS: SearchOptions = SearchOptions {
+    is_late_move_reduction_enabled: true,
+    is_futility_pruning_enabled: true,
+    evaluation_backend: EvaluationBackend::HandWritten,
+    is_card_flow_evaluation_enabled: true,
+    is_wizard_race_evaluation_enabled: true,
+    engine_parameters: DEFAULT_ENGINE_PARAMETERS,
+    contempt: 0,
+};
+
+// the deepest iteration that finishes within the limits is the one that gets played
+#[derive(Debug, Clone)]
+pub struct SearchLimits {
+    pub max_depth: usize,
+    pub deadline: Option<Instant>,
+    pub stop_signal: Option<Arc<AtomicBool>>,
+    pub max_num_of_nodes: Option<usize>,
+}
+
+pub fn create_search_limits(max_depth: usize) -> SearchLimits {
+    return SearchLimits {
+        max_depth,
+        deadline: None,
+        stop_signal: None,
+        max_num_of_nodes: None,
+    };
+}
+
+#[derive(Debug)]
+struct SearchContext<'a> {
+    pre_calculated: &'a PreCalculated,
+    game_history: &'a mut GameHistory,
+    search_options: SearchOptions,
+    search_limits: &'a SearchLimits,
+    root_player_id: usize,
+    num_of_nodes: usize,
+    num_of_visits: usize,
+    is_stopped: bool,
+    search_stats: SearchStats,
+}
+
+#[derive(Debug)]
+pub struct MinMaxNode {
+    pub depth: usize,
+    pub current_player_id: usize,
+    pub score: i32,
+    pub is_draw: bool,
+    pub is_expanded: bool,
+    // the window the node was last searched with and whether the last search skipped it
+    pub alpha: i32,
+    pub beta: i32,
+    pub is_pruned: bool,
+    pub command: String,
+    pub game_state: GameState,
+    pub child_nodes: Vec<MinMaxNode>,
+}
+
+pub fn create_minmax_node(
+    depth: usize,
+    current_player_id: usize,
+    score: i32,
+    command: String,
+    game_state: GameState,
+) -> MinMaxNode {
+    return MinMaxNode {
+        current_player_id,
+        depth,
+        game_state,
+        score,
+        is_draw: false,
+        is_expanded: false,
+        alpha: -1 * INFINITY,
+        beta: INFINITY,
+        is_pruned: false,
+        command,
+        child_nodes: vec![],
+    };
+}
+
+fn expand_min_max_node(node: &mut MinMaxNode, search_context: &mut SearchContext) {
+    search_context.search_stats.num_of_tree_probes += 1;
+    if node.is_expanded {
+        search_context.search_stats.num_of_tree_hits += 1;
+        return;
+    }
+    node.is_expanded = true;
+    let opponent_id = get_opponent_id(node.current_player_id);
+    let player_moves = get_possible_player_moves(
+        &node.game_state,
+        search_context.pre_calculated,
+        node.current_player_id,
+    );
+    for player_move in player_moves.iter() {
+        let (card_id, _) = get_player_card(
+            &node.game_state,
+            node.current_player_id,
+            player_move.card_index,
+        );
+
+        let next_game_state =
+            apply_player_move(&node.game_state, node.current_player_id, player_move);
+
+        let command = get_move_command(search_context.pre_calculated, card_id, player_move);
+
+        let mut child_node =
+            create_minmax_node(node.depth + 1, opponent_id, 0, command, next_game_state);
+
+        // the search treats the first repetition as a draw, otherwise it would happily
+        // shuffle pieces back and forth while the referee waits for the rule to trigger
+        let position_key = get_position_key(&next_game_state, opponent_id);
+        child_node.is_draw = !is_game_finished(&next_game_state)
+            && (get_num_of_position_repetitions(search_context.game_history, &position_key) > 0
+                || is_draw_by_turn_limit(search_context.game_history, child_node.depth));
+
+        search_context.num_of_nodes += 1;
+
+        node.child_nodes.push(child_node);
+    }
+}
+
+fn score_min_max_child_node(
+    child_node: &mut MinMaxNode,
+    search_context: &mut SearchContext,
+    depth: usize,
+    alpha: i32,
+    beta: i32,
+    is_maximizing_player: bool,
+) -> i32 {
+    let position_key = get_position_key(&child_node.game_state, child_node.current_player_id);
+    search_context.game_history.position_keys.push(position_key);
+    let node_eval = score_min_max_tree(
+        child_node,
+        search_context,
+        depth,
+        alpha,
+        beta,
+        is_maximizing_player,
+    );
+    search_context.game_history.position_keys.pop();
+    return node_eval;
+}
+
+// the score the search gives a position, the evaluation backend's from the root player's point of
+// view plus the card flow and wizard race terms it has enabled
+pub fn get_search_evaluation(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    search_options: &SearchOptions,
+    root_player_id: usize,
+    player_id_to_move: usize,
+) -> i32 {
+    let score = get_game_score_for_maximizing_player_with_backend(
+        game_state,
+        root_player_id,
+        search_options.evaluation_backend,
+        &search_options.engine_parameters,
+    );
+    if is_game_finished(game_state) {
+        return score;
+    }
+    let mut score_for_white = 0;
+    if search_options.is_card_flow_evaluation_enabled {
+        score_for_white += get_card_flow_score(
+            game_state,
+            pre_calculated,
+            player_id_to_move,
+            &search_options.engine_parameters,
+        );
+    }
+    if search_options.is_wizard_race_evaluation_enabled {
+        score_for_white += get_wizard_race_score(
+            game_state,
+            pre_calculated,
+            player_id_to_move,
+            &search_options.engine_parameters,
+        );
+    }
+    if root_player_id == WHITE_PLAYER_ID {
+        return score + score_for_white;
+    }
+    return score - score_for_white;
+}
+
+fn get_node_score(
+    search_context: &SearchContext,
+    game_state: &GameState,
+    player_id_to_move: usize,
+) -> i32 {
+    return get_search_evaluation(
+        game_state,
+        search_context.pre_calculated,
+        &search_context.search_options,
+        search_context.root_player_id,
+        player_id_to_move,
+    );
+}
+
+// futility pruning and late move reductions only ever skip quiet moves, captures, wizard moves,
+// wins and draws are always searched to full depth
+fn is_quiet_child_node(game_state: &GameState, child_node: &MinMaxNode) -> bool {
+    let opponent_id = child_node.current_player_id;
+    let player_id = get_opponent_id(opponent_id);
+    return !child_node.is_draw
+        && !is_game_finished(&child_node.game_state)
+        && get_player_piece_position(&child_node.game_state, player_id, GAME_STATE_WIZARD_OFFSET)
+            == get_player_piece_position(game_state, player_id, GAME_STATE_WIZARD_OFFSET)
+        && get_player_pieces_bitmask(&child_node.game_state, opponent_id).count_ones()
+            == get_player_pieces_bitmask(game_state, opponent_id).count_ones();
+}
+
+// root moves are never reduced, a reduced root move that fails low is gone for good, and a node
+// less than 2 plies deep has no plies left to take off whatever the parameters say
+fn is_late_move_reduction_applicable(
+    search_context: &SearchContext,
+    node_depth: usize,
+    depth: usize,
+    child_index: usize,
+    is_quiet: bool,
+) -> bool {
+    let engine_parameters = &search_context.search_options.engine_parameters;
+    return search_context.search_options.is_late_move_reduction_enabled
+        && is_quiet
+        && node_depth > 0
+        && depth >= 2
+        && depth as i32 >= engine_parameters.late_move_reduction_min_depth
+        && child_index as i32 >= engine_parameters.late_move_reduction_min_child_index;
+}
+
+// close to the leaves a quiet move whose static score stays on the wrong side of the window
+// even after granting it the material margin is not going to change the outcome
+fn get_futility_eval(
+    search_context: &SearchContext,
+    child_node: &MinMaxNode,
+    depth: usize,
+    child_index: usize,
+    is_quiet: bool,
+    is_maximizing_player: bool,
+) -> Option<i32> {
+    let engine_parameters = &search_context.search_options.engine_parameters;
+    if !search_context.search_options.is_futility_pruning_enabled
+        || !is_quiet
+        || child_index == 0
+        || depth < 2
+        || depth as i32 > engine_parameters.futility_pruning_max_depth
+    {
+        return None;
+    }
+    let margin = engine_parameters.futility_margin * (depth as i32 - 1);
+    let score = get_node_score(
+        search_context,
+        &child_node.game_state,
+        child_node.current_player_id,
+    );
+    if is_maximizing_player {
+        return Some(score + margin);
+    }
+    return Some(score - margin);
+}
+
+fn record_cutoff(search_context: &mut SearchContext, node_depth: usize) {
+    let num_of_cutoffs_per_ply = &mut search_context.search_stats.num_of_cutoffs_per_ply;
+    if num_of_cutoffs_per_ply.len() <= node_depth {
+        num_of_cutoffs_per_ply.resize(node_depth + 1, 0);
+    }
+    num_of_cutoffs_per_ply[node_depth] += 1;
+}
+
+// the first iteration always runs to completion so there is a move to play no matter how tight
+// the limits are
+fn is_search_stopped(search_context: &mut SearchContext, depth: usize) -> bool {
+    if search_context.is_stopped {
+        return true;
+    }
+    search_context.num_of_visits += 1;
+    if depth <= 1 || search_context.num_of_visits < NUM_OF_VISITS_BETWEEN_STOP_CHECKS {
+        return false;
+    }
+    search_context.num_of_visits = 0;
+    let limits = search_context.search_limits;
+    search_context.is_stopped = limits.deadline.is_some_and(|v| Instant::now() >= v)
+        || limits
+            .max_num_of_nodes
+            .is_some_and(|v| search_context.num_of_nodes >= v)
+        || limits
+            .stop_signal
+            .as_ref()
+            .is_some_and(|v| v.load(Ordering::Relaxed));
+    return search_context.is_stopped;
+}
+
+// principal variation search, the first child is expected to be the best one and gets the full
+// window, the rest only have to prove they are worse with a null window and are searched again
+// when they turn out to be better, late quiet moves first try to prove it at a reduced depth
+fn score_min_max_tree(
+    node: &mut MinMaxNode,
+    search_context: &mut SearchContext,
+    depth: usize,
+    alpha: i32,
+    beta: i32,
+    is_maximizing_player: bool,
+) -> i32 {
+    node.alpha = alpha;
+    node.beta = beta;
+    node.is_pruned = false;
+    if node.is_draw {
+        node.score = DRAW_SCORE - search_context.search_options.contempt;
+        return node.score;
+    }
+    if is_search_stopped(search_context, node.depth + depth) {
+        return node.score;
+    }
+    if depth == 0 || is_game_finished(&node.game_state) {
+        let score = get_node_score(search_context, &node.game_state, node.current_player_id);
+        node.score = score;
+        return score;
+    }
+    expand_min_max_node(node, search_context);
+    let game_state = node.game_state;
+    let node_depth = node.depth;
+    let mut best_child_index = 0;
+    // children left behind by a cutoff or futility pruning keep the mark, the rest clear it
+    // when they are searched
+    for child_node in node.child_nodes.iter_mut() {
+        child_node.is_pruned = true;
+    }
+    if is_maximizing_player {
+        let mut max_eval = -1 * INFINITY;
+        let mut max_alpha = alpha;
+        for (child_index, child_node) in node.child_nodes.iter_mut().enumerate() {
+            let is_quiet = is_quiet_child_node(&game_state, child_node);
+            let futility_eval = get_futility_eval(
+                search_context,
+                child_node,
+                depth,
+                child_index,
+                is_quiet,
+                true,
+            );
+            let mut node_eval = max_alpha + 1;
+            if let Some(futility_eval) = futility_eval.filter(|v| *v <= max_alpha) {
+                child_node.score = futility_eval;
+                node_eval = futility_eval;
+            } else {
+                if is_late_move_reduction_applicable(
+                    search_context,
+                    node_depth,
+                    depth,
+                    child_index,
+                    is_quiet,
+                ) {
+                    node_eval = score_min_max_child_node(
+                        child_node,
+                        search_context,
+                        depth - 2,
+                        max_alpha,
+                        max_alpha + 1,
+                        false,
+                    );
+                }
+                if child_index > 0 && node_eval > max_alpha {
+                    node_eval = score_min_max_child_node(
+                        child_node,
+                        search_context,
+                        depth - 1,
+                        max_alpha,
+                        max_alpha + 1,
+                        false,
+                    );
+                }
+                if child_index == 0 || (node_eval > max_alpha && node_eval < beta) {
+                    node_eval = score_min_max_child_node(
+                        child_node,
+                        search_context,
+                        depth - 1,
+                        max_alpha,
+                        beta,
+                        false,
+                    );
+                }
+            }
+            if node_eval > max_eval {
+                max_eval = node_eval;
+                best_child_index = child_index;
+            }
+            max_alpha = cmp::max(max_alpha, node_eval);
+            if beta <= max_alpha {
+                record_cutoff(search_context, node_depth);
+                break;
+            }
+        }
+        node.child_nodes[..=best_child_index].rotate_right(1);
+        node.score = max_eval;
+        return max_eval;
+    }
+    let mut min_eval = INFINITY;
+    let mut min_beta = beta;
+    for (child_index, child_node) in node.child_nodes.iter_mut().enumerate() {
+        let is_quiet = is_quiet_child_node(&game_state, child_node);
+        let futility_eval = get_futility_eval(
+            search_context,
+            child_node,
+            depth,
+            child_index,
+            is_quiet,
+            false,
+        );
+        let mut node_eval = min_beta - 1;
+        if let Some(futility_eval) = futility_eval.filter(|v| *v >= min_beta) {
+            child_node.score = futility_eval;
+            node_eval = futility_eval;
+        } else {
+            if is_late_move_reduction_applicable(
+                search_context,
+                node_depth,
+                depth,
+                child_index,
+                is_quiet,
+            ) {
+                node_eval = score_min_max_child_node(
+                    child_node,
+                    search_context,
+                    depth - 2,
+                    min_beta - 1,
+                    min_beta,
+                    true,
+                );
+            }
+            if child_index > 0 && node_eval < min_beta {
+                node_eval = score_min_max_child_node(
+                    child_node,
+                    search_context,
+                    depth - 1,
+                    min_beta - 1,
+                    min_beta,
+                    true,
+                );
+            }
+            if child_index == 0 || (node_eval < min_beta && node_eval > alpha) {
+                node_eval = score_min_max_child_node(
+                    child_node,
+                    search_context,
+                    depth - 1,
+                    alpha,
+                    min_beta,
+                    true,
+                );
+            }
+        }
+        if node_eval < min_eval {
+            min_eval = node_eval;
+            best_child_index = child_index;
+        }
+        min_beta = cmp::min(min_beta, node_eval);
+        if min_beta <= alpha {
+            record_cutoff(search_context, node_depth);
+            break;
+        }
+    }
+    node.child_nodes[..=best_child_index].rotate_right(1);
+    node.score = min_eval;
+    return min_eval;
+}
+
+// the previous iteration's score is usually close to the next one, so the root is searched with a
+// narrow window first and only falls back to the full window when the score lands outside of it
+fn score_min_max_tree_with_aspiration_window(
+    root_node: &mut MinMaxNode,
+    search_context: &mut SearchContext,
+    depth: usize,
+    previous_score: i32,
+) -> i32 {
+    let engine_parameters = search_context.search_options.engine_parameters;
+    if depth > 1 {
+        let alpha = previous_score - engine_parameters.aspiration_window;
+        let beta = previous_score + engine_parameters.aspiration_window;
+        let score = score_min_max_tree(root_node, search_context, depth, alpha, beta, true);
+        if score > alpha && score < beta {
+            return score;
+        }
+    }
+    return score_min_max_tree(
+        root_node,
+        search_context,
+        depth,
+        -1 * INFINITY,
+        INFINITY,
+        true,
+    );
+}
+
+pub fn get_next_command(node: &MinMaxNode) -> (String, i32, GameState) {
+    let mut max_score = -1 * INFINITY;
+    let mut next_command = "".to_string();
+    let mut next_game_state = node.game_state;
+    for child_node in node.child_nodes.iter() {
+        if child_node.score > max_score {
+            max_score = child_node.score;
+            next_command = child_node.command.clone();
+            next_game_state = child_node.game_state;
+        }
+    }
+    return (next_command, max_score, next_game_state);
+}
+
+pub fn get_target_depth(
+    num_of_possible_moves_in_total: usize,
+    engine_parameters: &EngineParameters,
+) -> usize {
+    let min_num_of_moves = engine_parameters.target_depth_min_num_of_moves;
+    if num_of_possible_moves_in_total as i32 >= min_num_of_moves[0] {
+        return 3;
+    }
+    if num_of_possible_moves_in_total as i32 >= min_num_of_moves[1] {
+        return 4;
+    }
+    if num_of_possible_moves_in_total as i32 >= min_num_of_moves[2] {
+        return 5;
+    }
+    return 6;
+}
+
+// iterative deepening, every finished iteration is reported and an iteration cut short by the
+// limits is thrown away in favour of the previous one, the searched tree is handed back for
+// inspection
+pub fn search_next_move_with_tree(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+    search_limits: &SearchLimits,
+    on_iteration_finished: &mut dyn FnMut(&SearchResult),
+) -> (SearchResult, MinMaxNode) {
+    let mut root_node: MinMaxNode =
+        create_minmax_node(0, player_id, 0, "".to_string(), *game_state);
+
+    let mut search_context = SearchContext {
+        pre_calculated,
+        game_history,
+        search_options,
+        search_limits,
+        root_player_id: player_id,
+        num_of_nodes: 0,
+        num_of_visits: 0,
+        is_stopped: false,
+        search_stats: create_search_stats(),
+    };
+
+    let mut search_result = SearchResult {
+        command: "".to_string(),
+        score: 0,
+        game_state: *game_state,
+        target_depth: 0,
+        num_of_nodes: 0,
+        search_stats: create_search_stats(),
+    };
+    for depth in 1..=cmp::min(search_limits.max_depth, MAX_SEARCH_DEPTH) {
+        score_min_max_tree_with_aspiration_window(
+            &mut root_node,
+            &mut search_context,
+            depth,
+            search_result.score,
+        );
+        if search_context.is_stopped {
+            break;
+        }
+
+        let (command, score, next_game_state) = get_next_command(&root_node);
+        search_result = SearchResult {
+            command,
+            score,
+            game_state: next_game_state,
+            target_depth: depth,
+            num_of_nodes: search_context.num_of_nodes,
+            search_stats: search_context.search_stats.clone(),
+        };
+        on_iteration_finished(&search_result);
+    }
+    search_result.num_of_nodes = search_context.num_of_nodes;
+    search_result.search_stats = search_context.search_stats;
+
+    return (search_result, root_node);
+}
+
+pub fn search_next_move_with_limits(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+    search_limits: &SearchLimits,
+    on_iteration_finished: &mut dyn FnMut(&SearchResult),
+) -> SearchResult {
+    let (search_result, _) = search_next_move_with_tree(
+        game_state,
+        player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        search_limits,
+        on_iteration_finished,
+    );
+    return search_result;
+}
+
+pub fn search_next_move_to_depth(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+    target_depth: usize,
+) -> SearchResult {
+    return search_next_move_with_limits(
+        game_state,
+        player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        &create_search_limits(target_depth),
+        &mut |_| {},
+    );
+}
+
+pub fn get_target_depth_for_game_state(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    engine_parameters: &EngineParameters,
+) -> usize {
+    let num_of_possible_moves_for_white =
+        get_num_of_estimated_moves_for_player(game_state, pre_calculated, WHITE_PLAYER_ID);
+    let num_of_possible_moves_for_black =
+        get_num_of_estimated_moves_for_player(game_state, pre_calculated, BLACK_PLAYER_ID);
+    let num_of_possible_moves_in_total =
+        num_of_possible_moves_for_white + num_of_possible_moves_for_black;
+
+    return get_target_depth(num_of_possible_moves_in_total, engine_parameters);
+}
+
+pub fn search_next_move(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+) -> SearchResult {
+    let target_depth = get_target_depth_for_game_state(
+        game_state,
+        pre_calculated,
+        &search_options.engine_parameters,
+    );
+
+    return search_next_move_to_depth(
+        game_state,
+        player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        target_depth,
+    );
+}
+
+// the target depth unless the deadline comes first, in which case the deepest finished iteration
+// is played
+pub fn search_next_move_before_deadline(
+    game_state: &GameState,
+    player_id: usize,
+    pre_calculated: &PreCalculated,
+    game_history: &mut GameHistory,
+    search_options: SearchOptions,
+    deadline: Instant,
+) -> SearchResult {
+    let mut search_limits = create_search_limits(get_target_depth_for_game_state(
+        game_state,
+        pre_calculated,
+        &search_options.engine_parameters,
+    ));
+    search_limits.deadline = Some(deadline);
+    return search_next_move_with_limits(
+        game_state,
+        player_id,
+        pre_calculated,
+        game_history,
+        search_options,
+        &search_limits,
+        &mut |_| {},
+    );
+}
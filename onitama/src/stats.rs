@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use crate::game_state::WHITE_PLAYER_ID;
+use crate::search::SearchResult;
+
+pub fn get_json_string(value: &str) -> String {
+    let mut json_string = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => json_string.push_str("\\\""),
+            '\\' => json_string.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json_string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json_string.push(c),
+        }
+    }
+    json_string.push('"');
+    return json_string;
+}
+
+pub fn get_tree_reuse_hit_rate(search_result: &SearchResult) -> f64 {
+    let search_stats = &search_result.search_stats;
+    if search_stats.num_of_tree_probes == 0 {
+        return 0.0;
+    }
+    return search_stats.num_of_tree_hits as f64 / search_stats.num_of_tree_probes as f64;
+}
+
+// one line of json per turn so that logs of many games can be aggregated with standard tools,
+// "tree_reuse_hit_rate" is the share of nodes an earlier iteration already expanded in the tree
+pub fn get_search_stats_json(
+    search_result: &SearchResult,
+    num_of_turns_played: usize,
+    player_id: usize,
+    duration: Duration,
+) -> String {
+    let num_of_nodes_per_second =
+        (search_result.num_of_nodes as f64 / duration.as_secs_f64().max(0.001)) as u64;
+    let num_of_cutoffs_per_ply: Vec<String> = search_result
+        .search_stats
+        .num_of_cutoffs_per_ply
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    let player = if player_id == WHITE_PLAYER_ID {
+        "white"
+    } else {
+        "black"
+    };
+    return format!(
+        "{{\"turn\":{},\"player\":\"{}\",\"depth\":{},\"nodes\":{},\"nps\":{},\"tree_reuse_hit_rate\":{:.4},\"cutoffs_per_ply\":[{}],\"best_move\":{},\"score\":{},\"time_ms\":{}}}",
+        num_of_turns_played,
+        player,
+        search_result.target_depth,
+        search_result.num_of_nodes,
+        num_of_nodes_per_second,
+        get_tree_reuse_hit_rate(search_result),
+        num_of_cutoffs_per_ply.join(","),
+        get_json_string(&search_result.command),
+        search_result.score,
+        duration.as_millis()
+    );
+}
@@ -0,0 +1,81 @@
+use crate::search::{MinMaxNode, INFINITY};
+
+fn get_bound_label(bound: i32) -> String {
+    if bound >= INFINITY {
+        return "inf".to_string();
+    }
+    if bound <= -1 * INFINITY {
+        return "-inf".to_string();
+    }
+    return bound.to_string();
+}
+
+fn get_node_label(node: &MinMaxNode) -> String {
+    let command = if node.command.is_empty() {
+        "root"
+    } else {
+        node.command.as_str()
+    };
+    let mut label = format!(
+        "{}\\nscore {}\\nalpha {} beta {}",
+        command.replace('"', "\\\""),
+        node.score,
+        get_bound_label(node.alpha),
+        get_bound_label(node.beta)
+    );
+    if node.is_draw {
+        label += "\\ndraw";
+    }
+    if node.is_pruned {
+        label += "\\npruned";
+    }
+    return label;
+}
+
+fn push_dot_node(
+    dot: &mut String,
+    node: &MinMaxNode,
+    node_id: usize,
+    num_of_plies_left: usize,
+    next_node_id: &mut usize,
+) {
+    let style = if node.is_pruned {
+        ", style=dashed, color=gray"
+    } else {
+        ""
+    };
+    dot.push_str(&format!(
+        "    n{} [label=\"{}\"{}];\n",
+        node_id,
+        get_node_label(node),
+        style
+    ));
+    // a pruned node's subtree is left over from an earlier iteration and says nothing about
+    // the last one
+    if num_of_plies_left == 0 || node.is_pruned {
+        return;
+    }
+    for child_node in node.child_nodes.iter() {
+        let child_node_id = *next_node_id;
+        *next_node_id += 1;
+        dot.push_str(&format!("    n{} -> n{};\n", node_id, child_node_id));
+        push_dot_node(
+            dot,
+            child_node,
+            child_node_id,
+            num_of_plies_left - 1,
+            next_node_id,
+        );
+    }
+}
+
+// the first child of every node is the one the search settled on, so the principal variation runs
+// down the left edge of the graph
+pub fn get_search_tree_dot(root_node: &MinMaxNode, num_of_plies: usize) -> String {
+    let mut dot =
+        String::from("digraph search_tree {\n    node [shape=box, fontname=monospace];\n");
+    let mut next_node_id = 1;
+    push_dot_node(&mut dot, root_node, 0, num_of_plies, &mut next_node_id);
+    dot.push_str("}\n");
+    return dot;
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::game_state::{
+    get_player_card, GameState, BLACK_PLAYER_SHRINE_MASK, DEFAULT_CARD_ROTATION, NUM_OF_CARDS,
+    NUM_OF_PLAYERS, WHITE_PLAYER_ID, WHITE_PLAYER_SHRINE_MASK,
+};
+use crate::move_generation::{get_possible_moves_from_position, PreCalculated};
+
+// distances are capped, a wizard further away than this is not racing anyone
+pub static MAX_WIZARD_RACE_DISTANCE: i32 = 8;
+
+// keyed by (player id, rotation of the player's cards, the lower and the higher card id in hand,
+// middle card id), holds the number of moves the wizard needs from each cell to the opposing
+// shrine
+pub type WizardRaceTable = HashMap<(usize, i32, i32, i32, i32), [i32; 25]>;
+
+// per cell the cells a card moves the wizard to
+type CellsReached = Vec<Vec<usize>>;
+
+// a card to play with the card states the opponent can leave behind afterwards
+type CardChoice<'a> = (&'a CellsReached, Vec<usize>);
+
+fn get_hand_key(card_id: i32, other_card_id: i32) -> (i32, i32) {
+    if card_id < other_card_id {
+        return (card_id, other_card_id);
+    }
+    return (other_card_id, card_id);
+}
+
+pub fn get_opposing_shrine_mask(player_id: usize) -> i32 {
+    if player_id == WHITE_PLAYER_ID {
+        return BLACK_PLAYER_SHRINE_MASK;
+    }
+    return WHITE_PLAYER_SHRINE_MASK;
+}
+
+// a card played goes to the opponent, the middle card comes to the player and the opponent hands
+// over one of theirs, the table assumes only the wizard moves and the opponent hands over
+// whichever card helps the most, so the distance is the fewest moves the race could take
+fn get_wizard_race_distances(
+    pre_calculated: &PreCalculated,
+    card_ids: &[i32],
+    player_id: usize,
+    card_rotation: i32,
+) -> Vec<((i32, i32, i32), [i32; 25])> {
+    let shrine_index = get_opposing_shrine_mask(player_id).trailing_zeros() as usize;
+    let mut card_states: Vec<(i32, i32, i32)> = vec![];
+    for (card_index, card_id) in card_ids.iter().enumerate() {
+        for other_card_id in card_ids[card_index + 1..].iter() {
+            for middle_card_id in card_ids.iter() {
+                if middle_card_id != card_id && middle_card_id != other_card_id {
+                    let (low_card_id, high_card_id) = get_hand_key(*card_id, *other_card_id);
+                    card_states.push((low_card_id, high_card_id, *middle_card_id));
+                }
+            }
+        }
+    }
+    let card_state_indexes: HashMap<(i32, i32, i32), usize> = card_states
+        .iter()
+        .enumerate()
+        .map(|(card_state_index, card_state)| (*card_state, card_state_index))
+        .collect();
+
+    // the cells each card reaches and the card states each choice leads to only depend on the
+    // cards, so the relaxing below never has to touch a hash map
+    let cells_reached_with_card: HashMap<i32, CellsReached> = card_ids
+        .iter()
+        .map(|card_id| {
+            let cells_reached: CellsReached = (0..25)
+                .map(|cell_index| {
+                    get_possible_moves_from_position(
+                        pre_calculated,
+                        1 << cell_index,
+                        *card_id,
+                        card_rotation,
+                    )
+                    .iter()
+                    .map(|v| v.trailing_zeros() as usize)
+                    .collect()
+                })
+                .collect();
+            return (*card_id, cells_reached);
+        })
+        .collect();
+    let mut card_choices: Vec<Vec<CardChoice>> = vec![];
+    for (low_card_id, high_card_id, middle_card_id) in card_states.iter() {
+        let opponent_card_ids: Vec<i32> = card_ids
+            .iter()
+            .copied()
+            .filter(|v| v != low_card_id && v != high_card_id && v != middle_card_id)
+            .collect();
+        let mut choices: Vec<CardChoice> = vec![];
+        for (card_id, other_card_id) in
+            [(*low_card_id, *high_card_id), (*high_card_id, *low_card_id)].iter()
+        {
+            let (next_low_card_id, next_high_card_id) =
+                get_hand_key(*other_card_id, *middle_card_id);
+            let next_card_state_indexes: Vec<usize> = opponent_card_ids
+                .iter()
+                .map(|v| card_state_indexes[&(next_low_card_id, next_high_card_id, *v)])
+                .collect();
+            choices.push((&cells_reached_with_card[card_id], next_card_state_indexes));
+        }
+        card_choices.push(choices);
+    }
+
+    let mut distances: Vec<[i32; 25]> = vec![[MAX_WIZARD_RACE_DISTANCE; 25]; card_states.len()];
+    for cell_distances in distances.iter_mut() {
+        cell_distances[shrine_index] = 0;
+    }
+
+    // relaxing until nothing changes, every sweep settles at least one more move of the race
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for (card_state_index, choices) in card_choices.iter().enumerate() {
+            for cell_index in 0..25 {
+                if cell_index == shrine_index {
+                    continue;
+                }
+                let mut distance = distances[card_state_index][cell_index];
+                for (cells_reached, next_card_state_indexes) in choices.iter() {
+                    for cell_index_after_move in cells_reached[cell_index].iter() {
+                        for next_card_state_index in next_card_state_indexes.iter() {
+                            distance = distance
+                                .min(distances[*next_card_state_index][*cell_index_after_move] + 1);
+                        }
+                    }
+                }
+                if distance < distances[card_state_index][cell_index] {
+                    distances[card_state_index][cell_index] = distance;
+                    is_changed = true;
+                }
+            }
+        }
+    }
+    return card_states.into_iter().zip(distances).collect();
+}
+
+// both players with the cards facing either way, the codingame input and the local tools do not
+// agree on how the cards are turned
+pub fn create_wizard_race_table(
+    pre_calculated: &PreCalculated,
+    card_ids: &[i32],
+) -> WizardRaceTable {
+    let mut wizard_race_table: WizardRaceTable = HashMap::new();
+    if card_ids.len() != NUM_OF_CARDS {
+        return wizard_race_table;
+    }
+    for player_id in 0..NUM_OF_PLAYERS {
+        for card_rotation in [DEFAULT_CARD_ROTATION, -1 * DEFAULT_CARD_ROTATION].iter() {
+            for ((low_card_id, high_card_id, middle_card_id), cell_distances) in
+                get_wizard_race_distances(pre_calculated, card_ids, player_id, *card_rotation)
+            {
+                wizard_race_table.insert(
+                    (
+                        player_id,
+                        *card_rotation,
+                        low_card_id,
+                        high_card_id,
+                        middle_card_id,
+                    ),
+                    cell_distances,
+                );
+            }
+        }
+    }
+    return wizard_race_table;
+}
+
+// the fewest moves the player's wizard needs to reach the opposing shrine with the cards in hand
+// and the given card in the middle when it is their turn
+pub fn get_wizard_race_distance(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+    wizard_position: i32,
+    middle_card_id: i32,
+) -> i32 {
+    if wizard_position == 0 {
+        return MAX_WIZARD_RACE_DISTANCE;
+    }
+    let (card_id, card_rotation) = get_player_card(game_state, player_id, 0);
+    let (other_card_id, _) = get_player_card(game_state, player_id, 1);
+    let (low_card_id, high_card_id) = get_hand_key(card_id, other_card_id);
+    return match pre_calculated.wizard_race_table.get(&(
+        player_id,
+        card_rotation,
+        low_card_id,
+        high_card_id,
+        middle_card_id,
+    )) {
+        Some(cell_distances) => cell_distances[wizard_position.trailing_zeros() as usize],
+        None => MAX_WIZARD_RACE_DISTANCE,
+    };
+}
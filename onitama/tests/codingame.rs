@@ -0,0 +1,95 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// the codingame game loop's decisions that do not need the referee, the emergency search and what
+// happens when the engine's move is not one the referee lists
+
+use std::time::{Duration, Instant};
+
+use onitama::codingame::{get_game_state_after_command, get_legal_command, search_turn_move};
+use onitama::game_state::{
+    create_initial_game_state, get_player_card, RulesVariant, WHITE_PLAYER_ID,
+};
+use onitama::history::{create_game_history, get_position_key, DEFAULT_DRAW_RULE};
+use onitama::move_generation::{
+    apply_player_move, create_pre_calculated_from_catalogue, get_move_command,
+    get_possible_player_moves,
+};
+use onitama::parser::{TurnAction, TurnInput};
+use onitama::search::DEFAULT_SEARCH_OPTIONS;
+
+static CARD_IDS: [i32; 5] = [1, 2, 3, 4, 5];
+
+fn create_turn_input(actions: Vec<TurnAction>) -> TurnInput {
+    return TurnInput {
+        received_at: Instant::now(),
+        board_rows: vec![],
+        cards: vec![],
+        actions,
+    };
+}
+
+#[test]
+fn turn_with_its_budget_used_up_falls_back_to_a_depth_1_search() {
+    let game_state = create_initial_game_state(&CARD_IDS, WHITE_PLAYER_ID, RulesVariant::Base);
+    let pre_calculated = create_pre_calculated_from_catalogue(&CARD_IDS);
+    let mut game_history = create_game_history(DEFAULT_DRAW_RULE);
+    game_history
+        .position_keys
+        .push(get_position_key(&game_state, WHITE_PLAYER_ID));
+
+    // a turn whose first line arrived a whole budget ago
+    let received_at = Instant::now() - Duration::from_millis(100);
+    let search_result = search_turn_move(
+        &game_state,
+        WHITE_PLAYER_ID,
+        &pre_calculated,
+        &mut game_history,
+        DEFAULT_SEARCH_OPTIONS,
+        received_at + Duration::from_millis(50),
+    );
+    assert_eq!(search_result.target_depth, 1);
+    assert!(!search_result.command.is_empty());
+
+    let search_result = search_turn_move(
+        &game_state,
+        WHITE_PLAYER_ID,
+        &pre_calculated,
+        &mut game_history,
+        DEFAULT_SEARCH_OPTIONS,
+        Instant::now() + Duration::from_millis(500),
+    );
+    assert!(search_result.target_depth > 1);
+}
+
+#[test]
+fn swapped_move_is_tracked_from_the_command_actually_sent() {
+    let game_state = create_initial_game_state(&CARD_IDS, WHITE_PLAYER_ID, RulesVariant::Base);
+    let pre_calculated = create_pre_calculated_from_catalogue(&CARD_IDS);
+    let player_move = get_possible_player_moves(&game_state, &pre_calculated, WHITE_PLAYER_ID)[0];
+    let (card_id, _) = get_player_card(&game_state, WHITE_PLAYER_ID, player_move.card_index);
+    let listed_command = get_move_command(&pre_calculated, card_id, &player_move);
+
+    let turn_input = create_turn_input(vec![TurnAction {
+        card_id,
+        command: listed_command.clone(),
+    }]);
+    assert_eq!(
+        get_legal_command(&turn_input, &listed_command),
+        listed_command
+    );
+
+    let command = get_legal_command(&turn_input, "99 A1A2");
+    assert_eq!(command, listed_command);
+    assert_eq!(
+        get_game_state_after_command(&game_state, WHITE_PLAYER_ID, &pre_calculated, &command),
+        Some(apply_player_move(
+            &game_state,
+            WHITE_PLAYER_ID,
+            &player_move
+        ))
+    );
+    assert_eq!(
+        get_game_state_after_command(&game_state, WHITE_PLAYER_ID, &pre_calculated, "99 A1A2"),
+        None
+    );
+}
@@ -0,0 +1,93 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// engine parameter overrides come from flags and the environment, a value the search cannot
+// work with has to be turned away before it gets there
+
+use onitama::engine_parameters::{
+    create_engine_parameters, get_engine_parameter, set_engine_parameter,
+    DEFAULT_ENGINE_PARAMETERS, ENGINE_PARAMETER_RANGES,
+};
+use onitama::game_state::{create_initial_game_state, RulesVariant, WHITE_PLAYER_ID};
+use onitama::history::{create_game_history, DEFAULT_DRAW_RULE};
+use onitama::move_generation::create_pre_calculated_from_catalogue;
+use onitama::search::{search_next_move_to_depth, SearchOptions, DEFAULT_SEARCH_OPTIONS};
+
+static CARD_IDS: [i32; 5] = [1, 2, 3, 4, 5];
+
+#[test]
+fn every_default_is_within_its_range() {
+    for (name, range) in ENGINE_PARAMETER_RANGES.iter() {
+        let value = get_engine_parameter(&DEFAULT_ENGINE_PARAMETERS, name).unwrap();
+        assert!(range.contains(&value), "{}={} out of range", name, value);
+    }
+}
+
+#[test]
+fn out_of_range_values_are_rejected() {
+    let mut engine_parameters = DEFAULT_ENGINE_PARAMETERS;
+    assert_eq!(
+        set_engine_parameter(&mut engine_parameters, "late_move_reduction_min_depth", "1"),
+        Err("1 for late_move_reduction_min_depth is out of range, expected 2 to 64".to_string())
+    );
+    assert!(set_engine_parameter(&mut engine_parameters, "futility_margin", "-100").is_err());
+    assert!(set_engine_parameter(&mut engine_parameters, "aspiration_window", "0").is_err());
+    assert!(set_engine_parameter(
+        &mut engine_parameters,
+        "white_end_game_target",
+        "0b1_00000_00000_00000_00000_00000"
+    )
+    .is_err());
+    assert_eq!(engine_parameters, DEFAULT_ENGINE_PARAMETERS);
+
+    assert_eq!(
+        set_engine_parameter(&mut engine_parameters, "infinity", "-5"),
+        Err("unknown engine parameter infinity".to_string())
+    );
+    assert_eq!(
+        set_engine_parameter(
+            &mut engine_parameters,
+            "white_end_game_target",
+            "0b11111_11111_11111_11111_11111"
+        ),
+        Ok(())
+    );
+
+    let (engine_parameters, errors) = create_engine_parameters(&[
+        ("futility_pruning_max_depth".to_string(), "-1".to_string()),
+        ("futility_margin".to_string(), "150".to_string()),
+    ]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        engine_parameters.futility_pruning_max_depth,
+        DEFAULT_ENGINE_PARAMETERS.futility_pruning_max_depth
+    );
+    assert_eq!(engine_parameters.futility_margin, 150);
+}
+
+#[test]
+fn smallest_late_move_reduction_depth_searches_safely() {
+    let mut engine_parameters = DEFAULT_ENGINE_PARAMETERS;
+    set_engine_parameter(&mut engine_parameters, "late_move_reduction_min_depth", "2").unwrap();
+    set_engine_parameter(
+        &mut engine_parameters,
+        "late_move_reduction_min_child_index",
+        "1",
+    )
+    .unwrap();
+    let search_options = SearchOptions {
+        engine_parameters,
+        ..DEFAULT_SEARCH_OPTIONS
+    };
+
+    let game_state = create_initial_game_state(&CARD_IDS, WHITE_PLAYER_ID, RulesVariant::Base);
+    let pre_calculated = create_pre_calculated_from_catalogue(&CARD_IDS);
+    let search_result = search_next_move_to_depth(
+        &game_state,
+        WHITE_PLAYER_ID,
+        &pre_calculated,
+        &mut create_game_history(DEFAULT_DRAW_RULE),
+        search_options,
+        4,
+    );
+    assert!(!search_result.command.is_empty());
+}
@@ -0,0 +1,152 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// the engine protocol driven line by line the way a script would, checking what it answers
+
+use std::sync::{Arc, Mutex};
+
+use onitama::engine_protocol::{
+    create_engine_session, handle_engine_command, wait_for_search, EngineSession,
+};
+use onitama::game_state::{create_initial_game_state, RulesVariant, WHITE_PLAYER_ID};
+use onitama::move_generation::{
+    create_pre_calculated_from_catalogue, get_perft, get_possible_player_moves,
+};
+use onitama::search::{get_search_evaluation, DEFAULT_SEARCH_OPTIONS};
+
+static CARD_IDS: [i32; 5] = [1, 2, 3, 4, 5];
+
+fn create_test_session() -> (EngineSession, Arc<Mutex<Vec<u8>>>) {
+    let output = Arc::new(Mutex::new(vec![]));
+    return (
+        create_engine_session(DEFAULT_SEARCH_OPTIONS, output.clone()),
+        output,
+    );
+}
+
+// runs the commands and returns every line written since the last call
+fn run_commands(
+    session: &mut EngineSession,
+    output: &Arc<Mutex<Vec<u8>>>,
+    lines: &[&str],
+) -> Vec<String> {
+    for line in lines.iter() {
+        assert!(handle_engine_command(session, line));
+    }
+    wait_for_search(session);
+    let text = String::from_utf8(output.lock().unwrap().split_off(0)).unwrap();
+    return text.lines().map(|v| v.to_string()).collect();
+}
+
+#[test]
+fn perft_lists_every_move_and_the_total() {
+    let (mut session, output) = create_test_session();
+    let lines = run_commands(
+        &mut session,
+        &output,
+        &["position startpos cards 1 2 3 4 5", "perft 2"],
+    );
+
+    let game_state = create_initial_game_state(&CARD_IDS, WHITE_PLAYER_ID, RulesVariant::Base);
+    let pre_calculated = create_pre_calculated_from_catalogue(&CARD_IDS);
+    let num_of_moves =
+        get_possible_player_moves(&game_state, &pre_calculated, WHITE_PLAYER_ID).len();
+    assert_eq!(lines.len(), num_of_moves + 1);
+    assert_eq!(
+        lines.last().unwrap(),
+        &format!(
+            "perft 2 {}",
+            get_perft(&game_state, &pre_calculated, WHITE_PLAYER_ID, 2)
+        )
+    );
+    let total: usize = lines[..num_of_moves]
+        .iter()
+        .map(|v| v.split(' ').nth(1).unwrap().parse::<usize>().unwrap())
+        .sum();
+    assert_eq!(lines.last().unwrap(), &format!("perft 2 {}", total));
+}
+
+#[test]
+fn go_depth_reports_every_iteration_and_a_legal_best_move() {
+    let (mut session, output) = create_test_session();
+    let lines = run_commands(
+        &mut session,
+        &output,
+        &["position startpos cards 1 2 3 4 5", "go depth 3"],
+    );
+
+    assert_eq!(lines.len(), 4);
+    for (line_index, line) in lines[..3].iter().enumerate() {
+        assert!(line.starts_with(&format!("info depth {} score ", line_index + 1)));
+    }
+    let best_move = lines[3].strip_prefix("bestmove ").unwrap();
+    assert!(lines[2].ends_with(&format!(" pv {}", best_move)));
+
+    let lines = run_commands(
+        &mut session,
+        &output,
+        &[
+            &format!("position startpos cards 1 2 3 4 5 moves {}", best_move),
+            "isready",
+        ],
+    );
+    assert_eq!(lines, vec!["readyok"]);
+}
+
+#[test]
+fn eval_prints_the_search_score_of_the_side_to_move() {
+    let (mut session, output) = create_test_session();
+    let lines = run_commands(
+        &mut session,
+        &output,
+        &[
+            "position board bbBbb/-----/-----/--w--/ww-Ww turn black cards 1 2 3 4 5",
+            "eval",
+        ],
+    );
+
+    let evaluation = get_search_evaluation(
+        &session.game_state,
+        &session.pre_calculated,
+        &session.search_options,
+        session.player_id_to_move,
+        session.player_id_to_move,
+    );
+    assert_eq!(lines, vec![format!("eval {}", evaluation)]);
+}
+
+#[test]
+fn invalid_commands_are_reported_and_keep_the_position() {
+    let (mut session, output) = create_test_session();
+    run_commands(
+        &mut session,
+        &output,
+        &["position startpos cards 1 2 3 4 5 moves"],
+    );
+    let game_state = session.game_state;
+
+    let lines = run_commands(
+        &mut session,
+        &output,
+        &[
+            "position startpos cards 1 2 3 3 5",
+            "position startpos cards 1 2 3 4 99",
+            "position board wwwww/-----/-----/-----/bbBbb",
+            "position startpos moves 9:A1A2",
+            "perft x",
+            "fly",
+        ],
+    );
+    assert_eq!(
+        lines,
+        vec![
+            "info string duplicate card in [1, 2, 3, 3, 5]",
+            "info string unknown card in [1, 2, 3, 4, 99]",
+            "info string white has 5 students, at most 4 fit on the board",
+            "info string illegal move: 9:A1A2",
+            "info string perft expects a depth",
+            "info string unknown command: fly",
+        ]
+    );
+    assert_eq!(session.game_state, game_state);
+    assert!(!handle_engine_command(&mut session, "quit"));
+}
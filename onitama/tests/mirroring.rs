@@ -0,0 +1,145 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// white and black are handled by separate masks and card rotations, so every position is checked
+// against its mirror image, the same game with the colours swapped and the board turned around,
+// which has to score exactly the other way round, the best moves themselves may differ between
+// equally scored moves as the mirror generates them in the opposite order
+
+use onitama::cards::DEFAULT_CARD_POOLS;
+use onitama::engine_parameters::DEFAULT_ENGINE_PARAMETERS;
+use onitama::evaluation::{get_card_flow_score, get_game_state_score, get_wizard_race_score};
+use onitama::game_state::{
+    create_initial_game_state, get_mirrored_game_state, get_opponent_id, is_game_finished,
+    GameState, RulesVariant, WHITE_PLAYER_ID,
+};
+use onitama::history::{create_game_history, DEFAULT_DRAW_RULE};
+use onitama::move_generation::{
+    apply_player_move, create_pre_calculated_from_catalogue, get_possible_player_moves,
+    PreCalculated,
+};
+use onitama::referee::{create_random, get_next_random, get_random_card_ids, Random};
+use onitama::search::{search_next_move_to_depth, DEFAULT_SEARCH_OPTIONS};
+
+static NUM_OF_RANDOM_POSITIONS: usize = 300;
+
+static MAX_NUM_OF_RANDOM_PLIES: usize = 40;
+
+static SEARCH_DEPTH: usize = 3;
+
+fn get_random_below(random: &mut Random, upper_bound: usize) -> usize {
+    return (get_next_random(random) % upper_bound as u64) as usize;
+}
+
+// random playouts from the opening reach the kind of positions the engine actually sees
+fn create_random_position(
+    random: &mut Random,
+    rules_variant: RulesVariant,
+) -> (GameState, PreCalculated, usize) {
+    let card_ids = get_random_card_ids(random, rules_variant, &DEFAULT_CARD_POOLS);
+    let pre_calculated = create_pre_calculated_from_catalogue(&card_ids);
+    let mut player_id = get_random_below(random, 2);
+    let mut game_state = create_initial_game_state(&card_ids, player_id, rules_variant);
+    for _ in 0..get_random_below(random, MAX_NUM_OF_RANDOM_PLIES) {
+        let player_moves = get_possible_player_moves(&game_state, &pre_calculated, player_id);
+        if player_moves.is_empty() || is_game_finished(&game_state) {
+            break;
+        }
+        let player_move = player_moves[get_random_below(random, player_moves.len())];
+        game_state = apply_player_move(&game_state, player_id, &player_move);
+        player_id = get_opponent_id(player_id);
+    }
+    return (game_state, pre_calculated, player_id);
+}
+
+fn get_score_for_white(score: i32, player_id: usize) -> i32 {
+    if player_id == WHITE_PLAYER_ID {
+        return score;
+    }
+    return -1 * score;
+}
+
+fn assert_mirrored_positions_are_negated(seed: u64, rules_variant: RulesVariant) {
+    let mut random = create_random(seed);
+    for _ in 0..NUM_OF_RANDOM_POSITIONS {
+        let (game_state, pre_calculated, player_id) =
+            create_random_position(&mut random, rules_variant);
+        let mirrored_game_state = get_mirrored_game_state(&game_state);
+        let mirrored_player_id = get_opponent_id(player_id);
+
+        assert_eq!(get_mirrored_game_state(&mirrored_game_state), game_state);
+        assert_eq!(
+            get_game_state_score(&mirrored_game_state),
+            -1 * get_game_state_score(&game_state),
+            "{:?}",
+            game_state
+        );
+        if is_game_finished(&game_state) {
+            continue;
+        }
+        assert_eq!(
+            get_card_flow_score(
+                &mirrored_game_state,
+                &pre_calculated,
+                mirrored_player_id,
+                &DEFAULT_ENGINE_PARAMETERS
+            ),
+            -1 * get_card_flow_score(
+                &game_state,
+                &pre_calculated,
+                player_id,
+                &DEFAULT_ENGINE_PARAMETERS
+            ),
+            "{:?}",
+            game_state
+        );
+        assert_eq!(
+            get_wizard_race_score(
+                &mirrored_game_state,
+                &pre_calculated,
+                mirrored_player_id,
+                &DEFAULT_ENGINE_PARAMETERS
+            ),
+            -1 * get_wizard_race_score(
+                &game_state,
+                &pre_calculated,
+                player_id,
+                &DEFAULT_ENGINE_PARAMETERS
+            ),
+            "{:?}",
+            game_state
+        );
+
+        let search_result = search_next_move_to_depth(
+            &game_state,
+            player_id,
+            &pre_calculated,
+            &mut create_game_history(DEFAULT_DRAW_RULE),
+            DEFAULT_SEARCH_OPTIONS,
+            SEARCH_DEPTH,
+        );
+        let mirrored_search_result = search_next_move_to_depth(
+            &mirrored_game_state,
+            mirrored_player_id,
+            &pre_calculated,
+            &mut create_game_history(DEFAULT_DRAW_RULE),
+            DEFAULT_SEARCH_OPTIONS,
+            SEARCH_DEPTH,
+        );
+        assert_eq!(
+            get_score_for_white(mirrored_search_result.score, mirrored_player_id),
+            -1 * get_score_for_white(search_result.score, player_id),
+            "{:?}",
+            game_state
+        );
+    }
+}
+
+#[test]
+fn mirrored_positions_score_negated_with_base_cards() {
+    assert_mirrored_positions_are_negated(1, RulesVariant::Base);
+}
+
+#[test]
+fn mirrored_positions_score_negated_with_wind_cards() {
+    assert_mirrored_positions_are_negated(2, RulesVariant::WayOfTheWind);
+}
@@ -0,0 +1,443 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// compares the bitboard move generator against a deliberately simple generator working on
+// board coordinates, over random positions and card sets
+
+use onitama::cards::{
+    get_card_pool_card_ids, CardCoordinates, CardPool, BASE_CARD_CATALOGUE,
+    SENSEIS_PATH_CARD_CATALOGUE, WIND_CARD_CATALOGUE,
+};
+use onitama::game_state::{
+    create_game_state_from_board, get_middle_card, get_player_card, get_player_piece_position,
+    get_wind_spirit_position, GameState, RulesVariant, BLACK_PLAYER_ID, GAME_STATE_WIZARD_OFFSET,
+    NUM_OF_CARDS_PER_PLAYER, NUM_OF_PIECES_PER_PLAYER, WHITE_PLAYER_ID,
+};
+use onitama::move_generation::{
+    apply_player_move, create_pre_calculated_from_catalogue, get_move_command,
+    get_possible_player_moves, PreCalculated,
+};
+use onitama::referee::{create_random, get_next_random, Random};
+
+static NUM_OF_RANDOM_POSITIONS: usize = 2000;
+
+static EMPTY_CELL: char = '-';
+
+static WIND_SPIRIT_CELL: char = 'S';
+
+type NaiveCard = (i32, i32);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct NaivePosition {
+    // indexed by [y][x], y = 0 being rank 1
+    cells: [[char; 5]; 5],
+    hands: [Vec<NaiveCard>; 2],
+    middle_card: NaiveCard,
+}
+
+fn get_random_below(random: &mut Random, upper_bound: usize) -> usize {
+    return (get_next_random(random) % upper_bound as u64) as usize;
+}
+
+fn get_cell_name(x: i32, y: i32) -> String {
+    return format!("{}{}", (b'A' + x as u8) as char, y + 1);
+}
+
+fn is_on_board(x: i32, y: i32) -> bool {
+    return (0..5).contains(&x) && (0..5).contains(&y);
+}
+
+fn get_player_cells(player_id: usize) -> (char, char) {
+    if player_id == WHITE_PLAYER_ID {
+        return ('w', 'W');
+    }
+    return ('b', 'B');
+}
+
+fn is_wizard_cell(cell: char) -> bool {
+    return cell == 'W' || cell == 'B';
+}
+
+fn is_student_cell(cell: char) -> bool {
+    return cell == 'w' || cell == 'b';
+}
+
+fn get_card_coordinates(card_id: i32) -> (CardCoordinates, Option<CardCoordinates>) {
+    if let Some((_, _, coordinates)) = BASE_CARD_CATALOGUE
+        .iter()
+        .chain(SENSEIS_PATH_CARD_CATALOGUE.iter())
+        .find(|v| v.0 == card_id)
+    {
+        return (*coordinates, None);
+    }
+    let (_, _, coordinates, wind_spirit_coordinates) =
+        WIND_CARD_CATALOGUE.iter().find(|v| v.0 == card_id).unwrap();
+    return (*coordinates, Some(*wind_spirit_coordinates));
+}
+
+fn find_wind_spirit(cells: &[[char; 5]; 5]) -> Option<(i32, i32)> {
+    for (y, row) in cells.iter().enumerate() {
+        if let Some(x) = row.iter().position(|v| *v == WIND_SPIRIT_CELL) {
+            return Some((x as i32, y as i32));
+        }
+    }
+    return None;
+}
+
+// moves the wind spirit, a student standing on the target swaps places with it
+fn move_naive_wind_spirit(cells: &mut [[char; 5]; 5], from: (i32, i32), to: (i32, i32)) {
+    let target_cell = cells[to.1 as usize][to.0 as usize];
+    cells[from.1 as usize][from.0 as usize] = if is_student_cell(target_cell) {
+        target_cell
+    } else {
+        EMPTY_CELL
+    };
+    cells[to.1 as usize][to.0 as usize] = WIND_SPIRIT_CELL;
+}
+
+fn get_position_after_card_exchange(
+    position: &NaivePosition,
+    cells: [[char; 5]; 5],
+    player_id: usize,
+    card: NaiveCard,
+) -> NaivePosition {
+    let mut next_position = NaivePosition {
+        cells,
+        hands: position.hands.clone(),
+        middle_card: (card.0, -1 * card.1),
+    };
+    let hand = &mut next_position.hands[player_id];
+    let card_index = hand.iter().position(|v| *v == card).unwrap();
+    hand[card_index] = position.middle_card;
+    hand.sort();
+    return next_position;
+}
+
+fn get_naive_player_moves(
+    position: &NaivePosition,
+    player_id: usize,
+) -> Vec<(String, NaivePosition)> {
+    let mut player_moves: Vec<(String, NaivePosition)> = vec![];
+    let (own_student_cell, own_wizard_cell) = get_player_cells(player_id);
+    let wind_spirit = find_wind_spirit(&position.cells);
+
+    for card in position.hands[player_id].iter() {
+        let (card_id, card_rotation) = *card;
+        let (coordinates, wind_spirit_coordinates) = get_card_coordinates(card_id);
+
+        for y in 0..5 {
+            for x in 0..5 {
+                let piece_cell = position.cells[y as usize][x as usize];
+                if piece_cell != own_student_cell && piece_cell != own_wizard_cell {
+                    continue;
+                }
+                for [dx, dy] in coordinates.iter() {
+                    if *dx == 0 && *dy == 0 {
+                        continue;
+                    }
+                    let (to_x, to_y) = (x + card_rotation * dx, y + card_rotation * dy);
+                    if !is_on_board(to_x, to_y) {
+                        continue;
+                    }
+                    let target_cell = position.cells[to_y as usize][to_x as usize];
+                    if target_cell == own_student_cell
+                        || target_cell == own_wizard_cell
+                        || target_cell == WIND_SPIRIT_CELL
+                    {
+                        continue;
+                    }
+
+                    let mut cells = position.cells;
+                    cells[y as usize][x as usize] = EMPTY_CELL;
+                    cells[to_y as usize][to_x as usize] = piece_cell;
+                    let command = format!(
+                        "{} {}{}",
+                        card_id,
+                        get_cell_name(x, y),
+                        get_cell_name(to_x, to_y)
+                    );
+
+                    let mut num_of_wind_spirit_moves = 0;
+                    if let (Some((spirit_x, spirit_y)), Some(wind_spirit_coordinates)) =
+                        (wind_spirit, wind_spirit_coordinates)
+                    {
+                        for [spirit_dx, spirit_dy] in wind_spirit_coordinates.iter() {
+                            if *spirit_dx == 0 && *spirit_dy == 0 {
+                                continue;
+                            }
+                            let spirit_to_x = spirit_x + card_rotation * spirit_dx;
+                            let spirit_to_y = spirit_y + card_rotation * spirit_dy;
+                            if !is_on_board(spirit_to_x, spirit_to_y)
+                                || is_wizard_cell(cells[spirit_to_y as usize][spirit_to_x as usize])
+                            {
+                                continue;
+                            }
+                            let mut cells_after_wind_spirit_move = cells;
+                            move_naive_wind_spirit(
+                                &mut cells_after_wind_spirit_move,
+                                (spirit_x, spirit_y),
+                                (spirit_to_x, spirit_to_y),
+                            );
+                            player_moves.push((
+                                format!(
+                                    "{} {}{}",
+                                    command,
+                                    get_cell_name(spirit_x, spirit_y),
+                                    get_cell_name(spirit_to_x, spirit_to_y)
+                                ),
+                                get_position_after_card_exchange(
+                                    position,
+                                    cells_after_wind_spirit_move,
+                                    player_id,
+                                    *card,
+                                ),
+                            ));
+                            num_of_wind_spirit_moves += 1;
+                        }
+                    }
+                    if num_of_wind_spirit_moves == 0 {
+                        player_moves.push((
+                            command,
+                            get_position_after_card_exchange(position, cells, player_id, *card),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // regular cards can move the wind spirit instead of a piece
+        let (spirit_x, spirit_y) = match wind_spirit {
+            Some(wind_spirit) if wind_spirit_coordinates.is_none() => wind_spirit,
+            _ => continue,
+        };
+        for [dx, dy] in coordinates.iter() {
+            if *dx == 0 && *dy == 0 {
+                continue;
+            }
+            let (to_x, to_y) = (spirit_x + card_rotation * dx, spirit_y + card_rotation * dy);
+            if !is_on_board(to_x, to_y)
+                || is_wizard_cell(position.cells[to_y as usize][to_x as usize])
+            {
+                continue;
+            }
+            let mut cells = position.cells;
+            move_naive_wind_spirit(&mut cells, (spirit_x, spirit_y), (to_x, to_y));
+            player_moves.push((
+                format!(
+                    "{} {}{}",
+                    card_id,
+                    get_cell_name(spirit_x, spirit_y),
+                    get_cell_name(to_x, to_y)
+                ),
+                get_position_after_card_exchange(position, cells, player_id, *card),
+            ));
+        }
+    }
+
+    // without a legal move the player passes and still gives up one of the cards
+    if player_moves.is_empty() {
+        for card in position.hands[player_id].iter() {
+            player_moves.push((
+                format!("{} PASS", card.0),
+                get_position_after_card_exchange(position, position.cells, player_id, *card),
+            ));
+        }
+    }
+
+    player_moves.sort();
+    return player_moves;
+}
+
+fn get_naive_position_from_game_state(game_state: &GameState) -> NaivePosition {
+    let mut cells = [[EMPTY_CELL; 5]; 5];
+    let mut set_cell = |position: i32, cell: char| {
+        if position == 0 {
+            return;
+        }
+        let bit_index = position.trailing_zeros() as usize;
+        cells[bit_index / 5][bit_index % 5] = cell;
+    };
+    for player_id in [WHITE_PLAYER_ID, BLACK_PLAYER_ID].iter() {
+        let (student_cell, wizard_cell) = get_player_cells(*player_id);
+        for piece_index in 0..NUM_OF_PIECES_PER_PLAYER {
+            let piece_position = get_player_piece_position(game_state, *player_id, piece_index);
+            if piece_index == GAME_STATE_WIZARD_OFFSET {
+                set_cell(piece_position, wizard_cell);
+            } else {
+                set_cell(piece_position, student_cell);
+            }
+        }
+    }
+    set_cell(get_wind_spirit_position(game_state), WIND_SPIRIT_CELL);
+
+    let mut hands: [Vec<NaiveCard>; 2] = [vec![], vec![]];
+    for (player_id, hand) in hands.iter_mut().enumerate() {
+        for card_index in 0..NUM_OF_CARDS_PER_PLAYER {
+            hand.push(get_player_card(game_state, player_id, card_index));
+        }
+        hand.sort();
+    }
+    return NaivePosition {
+        cells,
+        hands,
+        middle_card: get_middle_card(game_state),
+    };
+}
+
+fn get_bitboard_player_moves(
+    game_state: &GameState,
+    pre_calculated: &PreCalculated,
+    player_id: usize,
+) -> Vec<(String, NaivePosition)> {
+    let mut player_moves: Vec<(String, NaivePosition)> =
+        get_possible_player_moves(game_state, pre_calculated, player_id)
+            .iter()
+            .map(|player_move| {
+                let (card_id, _) = get_player_card(game_state, player_id, player_move.card_index);
+                return (
+                    get_move_command(pre_calculated, card_id, player_move),
+                    get_naive_position_from_game_state(&apply_player_move(
+                        game_state,
+                        player_id,
+                        player_move,
+                    )),
+                );
+            })
+            .collect();
+    player_moves.sort();
+    return player_moves;
+}
+
+// wizards never start on the opponent's shrine as that position would already be decided
+fn create_random_board(random: &mut Random, rules_variant: RulesVariant) -> String {
+    let mut cells: Vec<char> = vec![EMPTY_CELL; 25];
+    let mut free_cell_indexes: Vec<usize> = (0..25).collect();
+    let mut take_random_cell = |random: &mut Random, excluded_cell_index: usize| -> usize {
+        loop {
+            let index = get_random_below(random, free_cell_indexes.len());
+            if free_cell_indexes[index] == excluded_cell_index {
+                continue;
+            }
+            return free_cell_indexes.swap_remove(index);
+        }
+    };
+
+    cells[take_random_cell(random, 22)] = 'W';
+    cells[take_random_cell(random, 2)] = 'B';
+    for student_cell in ['w', 'b'].iter() {
+        let num_of_students = get_random_below(random, 5);
+        for _ in 0..num_of_students {
+            cells[take_random_cell(random, 25)] = *student_cell;
+        }
+    }
+    if rules_variant == RulesVariant::WayOfTheWind {
+        cells[take_random_cell(random, 25)] = WIND_SPIRIT_CELL;
+    }
+
+    // board rows run from rank 5 down to rank 1
+    let board_rows: Vec<String> = (0..5)
+        .rev()
+        .map(|y| cells[y * 5..y * 5 + 5].iter().collect())
+        .collect();
+    return board_rows.join("/");
+}
+
+fn get_dealable_card_ids(rules_variant: RulesVariant, card_pools: &[CardPool]) -> Vec<i32> {
+    let mut card_ids: Vec<i32> = card_pools
+        .iter()
+        .flat_map(|v| get_card_pool_card_ids(*v))
+        .collect();
+    if rules_variant == RulesVariant::WayOfTheWind {
+        card_ids.extend(WIND_CARD_CATALOGUE.iter().map(|v| v.0));
+    }
+    return card_ids;
+}
+
+fn create_random_card_ids(
+    random: &mut Random,
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+) -> [i32; 5] {
+    let mut card_ids = get_dealable_card_ids(rules_variant, card_pools);
+    let mut random_card_ids: [i32; 5] = [0; 5];
+    for random_card_id in random_card_ids.iter_mut() {
+        *random_card_id = card_ids.swap_remove(get_random_below(random, card_ids.len()));
+    }
+    return random_card_ids;
+}
+
+// the move tables are built once for every card the positions can be dealt, they are keyed by card
+// so any five of them generate the same moves as tables built for just those five
+fn create_card_pool_pre_calculated(
+    rules_variant: RulesVariant,
+    card_pools: &[CardPool],
+) -> PreCalculated {
+    return create_pre_calculated_from_catalogue(&get_dealable_card_ids(rules_variant, card_pools));
+}
+
+fn assert_generators_agree(seed: u64, rules_variant: RulesVariant, card_pools: &[CardPool]) {
+    let pre_calculated = create_card_pool_pre_calculated(rules_variant, card_pools);
+    let mut random = create_random(seed);
+    for _ in 0..NUM_OF_RANDOM_POSITIONS {
+        let board = create_random_board(&mut random, rules_variant);
+        let card_ids = create_random_card_ids(&mut random, rules_variant, card_pools);
+        let player_id = get_random_below(&mut random, 2);
+
+        let game_state = create_game_state_from_board(&board, &card_ids, player_id, rules_variant);
+        let position = get_naive_position_from_game_state(&game_state);
+
+        assert_eq!(
+            get_bitboard_player_moves(&game_state, &pre_calculated, player_id),
+            get_naive_player_moves(&position, player_id),
+            "board: {}, cards: {:?}, player: {}",
+            board,
+            card_ids,
+            player_id
+        );
+    }
+}
+
+#[test]
+fn bitboard_moves_match_naive_moves_for_base_cards() {
+    assert_generators_agree(1, RulesVariant::Base, &[CardPool::Base]);
+}
+
+#[test]
+fn bitboard_moves_match_naive_moves_for_wind_cards() {
+    assert_generators_agree(2, RulesVariant::WayOfTheWind, &[CardPool::Base]);
+}
+
+#[test]
+fn bitboard_moves_match_naive_moves_for_senseis_path_cards() {
+    assert_generators_agree(3, RulesVariant::Base, &[CardPool::SenseisPath]);
+}
+
+#[test]
+fn bitboard_moves_match_naive_moves_for_mixed_card_pools_with_wind_cards() {
+    assert_generators_agree(
+        4,
+        RulesVariant::WayOfTheWind,
+        &[CardPool::Base, CardPool::SenseisPath],
+    );
+}
+
+#[test]
+fn rotated_card_moves_do_not_wrap_around_the_a_file() {
+    // a rotated rabbit from A3 would land on E1 and off the board if the shifts were not masked
+    let card_ids = [1, 2, 4, 5, 6];
+    let game_state = create_game_state_from_board(
+        "--B--/-----/b----/-----/--W--",
+        &card_ids,
+        BLACK_PLAYER_ID,
+        RulesVariant::Base,
+    );
+    let commands: Vec<String> = get_bitboard_player_moves(
+        &game_state,
+        &create_pre_calculated_from_catalogue(&card_ids),
+        BLACK_PLAYER_ID,
+    )
+    .into_iter()
+    .map(|v| v.0)
+    .filter(|command| command.starts_with("4 A3"))
+    .collect();
+    assert_eq!(commands, vec!["4 A3B4".to_string()]);
+}
@@ -0,0 +1,222 @@
+#![allow(clippy::needless_return, clippy::neg_multiply)]
+
+// the turn parser has to survive anything the input throws at it, malformed turns are reported
+// and still give a game state to fall back on
+
+use onitama::game_state::{
+    check_board_piece_counts, get_player_piece_position, RulesVariant, BLACK_PLAYER_ID,
+    GAME_STATE_WIZARD_OFFSET, NUM_OF_STUDENTS_PER_PLAYER, WHITE_PLAYER_ID,
+};
+use onitama::parser::{
+    create_game_state_from_turn, create_input_reader, read_turn, ParseError, TurnInput,
+};
+
+static BOARD_LINES: &str = "bbBbb
+-----
+-----
+-----
+wwWww
+";
+
+static CARD_LINES: &str = "0 1 0 1 0 -1 0 0 0 0
+0 2 1 0 -1 0 0 0 0 0
+1 3 0 -1 0 1 0 0 0 0
+1 4 1 1 -1 -1 0 0 0 0
+-1 5 0 2 0 0 0 0 0 0
+";
+
+fn get_turn_input_text(board_rows: &[&str]) -> String {
+    return format!("{}\n{}0\n", board_rows.join("\n"), CARD_LINES);
+}
+
+fn read_test_turn(input: &str) -> (TurnInput, Vec<ParseError>) {
+    let mut input_reader = create_input_reader(input.as_bytes());
+    return read_turn(&mut input_reader, RulesVariant::Base).unwrap();
+}
+
+fn get_error_fields(parse_errors: &[ParseError]) -> Vec<(usize, &str, &str)> {
+    return parse_errors
+        .iter()
+        .map(|v| (v.line_number, v.expected.as_str(), v.found.as_str()))
+        .collect();
+}
+
+#[test]
+fn overfull_board_is_reported_and_trimmed_to_the_piece_slots() {
+    let input = get_turn_input_text(&["wwwww", "wwwww", "wwwww", "wwwww", "wwwww"]);
+    let mut input_reader = create_input_reader(input.as_bytes());
+    let (turn_input, parse_errors) = read_turn(&mut input_reader, RulesVariant::Base).unwrap();
+
+    // the first row keeps four students, every student after them is one error
+    assert_eq!(parse_errors.len(), 25 - NUM_OF_STUDENTS_PER_PLAYER);
+    assert_eq!(parse_errors[0].line_number, 1);
+    assert_eq!(parse_errors[0].found, "w number 5");
+    assert_eq!(turn_input.board_rows[0], "wwww-");
+    assert!(turn_input.board_rows[1..].iter().all(|v| v == "-----"));
+    assert!(check_board_piece_counts(turn_input.board_rows.iter().map(|v| v.as_str())).is_ok());
+
+    let (game_state, _, _) = create_game_state_from_turn(&turn_input, RulesVariant::Base);
+    assert_eq!(
+        get_player_piece_position(&game_state, WHITE_PLAYER_ID, GAME_STATE_WIZARD_OFFSET),
+        0
+    );
+}
+
+#[test]
+fn second_wizard_is_reported() {
+    let input = get_turn_input_text(&["bbBbb", "--B--", "-----", "-----", "wwWww"]);
+    let mut input_reader = create_input_reader(input.as_bytes());
+    let (turn_input, parse_errors) = read_turn(&mut input_reader, RulesVariant::Base).unwrap();
+
+    assert_eq!(parse_errors.len(), 1);
+    assert_eq!(parse_errors[0].line_number, 2);
+    assert_eq!(turn_input.board_rows[1], "-----");
+    assert_ne!(
+        get_player_piece_position(
+            &create_game_state_from_turn(&turn_input, RulesVariant::Base).0,
+            BLACK_PLAYER_ID,
+            GAME_STATE_WIZARD_OFFSET
+        ),
+        0
+    );
+}
+
+#[test]
+fn board_piece_counts_are_checked_per_player() {
+    assert!(check_board_piece_counts(
+        ["bbBbb", "-----", "-----", "-----", "wwWww"]
+            .iter()
+            .copied()
+    )
+    .is_ok());
+    assert_eq!(
+        check_board_piece_counts(
+            ["bbBbb", "b----", "-----", "-----", "wwWww"]
+                .iter()
+                .copied()
+        ),
+        Err("black has 5 students, at most 4 fit on the board".to_string())
+    );
+    assert_eq!(
+        check_board_piece_counts(
+            ["bbBbb", "-----", "-----", "-----", "wWWww"]
+                .iter()
+                .copied()
+        ),
+        Err("white has 2 wizards, expected at most 1".to_string())
+    );
+}
+
+#[test]
+fn malformed_board_rows_are_reported_and_padded() {
+    let input = get_turn_input_text(&["bbBb", "------", "--X--", "-----", "wwWww"]);
+    let (turn_input, parse_errors) = read_test_turn(&input);
+
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![
+            (1, "a board row of 5 cells", "bbBb"),
+            (2, "a board row of 5 cells", "------"),
+            (3, "cell 3 to be one of wWbB-", "X"),
+        ]
+    );
+    assert_eq!(turn_input.board_rows[0], "bbBb-");
+    assert_eq!(turn_input.board_rows[1], "-----");
+    assert_eq!(turn_input.board_rows[2], "-----");
+}
+
+#[test]
+fn malformed_card_lines_are_reported() {
+    let input = format!(
+        "{}0 x 0 1 0 -1 0 0 0 0
+0 2 1 0 y 0 0 0 0 0
+2 3 0 -1 0 1 0 0 0 0
+1 4 1 1
+-1 5 0 2 0 0 0 0 0 0
+0
+",
+        BOARD_LINES
+    );
+    let (turn_input, parse_errors) = read_test_turn(&input);
+
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![
+            (6, "card id", "x"),
+            (7, "dx2", "y"),
+            (8, "card owner 0, 1 or -1", "2"),
+            (9, "10 card values", "1 4 1 1"),
+            (9, "dx2", ""),
+            (9, "dy2", ""),
+            (9, "dx3", ""),
+            (9, "dy3", ""),
+            (9, "dx4", ""),
+            (9, "dy4", ""),
+            (
+                10,
+                "two cards per player and one middle card",
+                "2 white, 1 black, 1 middle"
+            ),
+        ]
+    );
+    assert_eq!(turn_input.cards.len(), 4);
+}
+
+#[test]
+fn malformed_actions_are_reported() {
+    let input = format!("{}{}3\n1 A1A2\nx B1B2\nPASS\n", BOARD_LINES, CARD_LINES);
+    let (turn_input, parse_errors) = read_test_turn(&input);
+
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![
+            (13, "card id", "x"),
+            (14, "an action as card id and move", "PASS"),
+        ]
+    );
+    assert_eq!(turn_input.actions.len(), 1);
+    assert_eq!(turn_input.actions[0].command, "1 A1A2");
+
+    let (_, parse_errors) = read_test_turn(&format!("{}{}many\n", BOARD_LINES, CARD_LINES));
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![(11, "action count", "many")]
+    );
+}
+
+#[test]
+fn action_count_beyond_the_input_keeps_the_turn() {
+    let input = format!("{}{}3\n1 A1A2\n", BOARD_LINES, CARD_LINES);
+    let (turn_input, parse_errors) = read_test_turn(&input);
+
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![(13, "3 actions", "1 before the input ended")]
+    );
+    assert_eq!(turn_input.actions.len(), 1);
+}
+
+#[test]
+fn absurd_action_count_does_not_consume_the_next_turn() {
+    let next_turn = format!("{}{}1\n2 E1E2\n", BOARD_LINES, CARD_LINES);
+    let input = format!("{}{}1000000\n{}", BOARD_LINES, CARD_LINES, next_turn);
+    let mut input_reader = create_input_reader(input.as_bytes());
+
+    let (turn_input, parse_errors) = read_turn(&mut input_reader, RulesVariant::Base).unwrap();
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![(11, "an action count from 0 to 192", "1000000")]
+    );
+    assert!(turn_input.actions.is_empty());
+
+    let (turn_input, parse_errors) = read_turn(&mut input_reader, RulesVariant::Base).unwrap();
+    assert!(parse_errors.is_empty());
+    assert_eq!(turn_input.board_rows[0], "bbBbb");
+    assert_eq!(turn_input.actions[0].command, "2 E1E2");
+
+    let (_, parse_errors) = read_test_turn(&format!("{}{}-1\n", BOARD_LINES, CARD_LINES));
+    assert_eq!(
+        get_error_fields(&parse_errors),
+        vec![(11, "an action count from 0 to 192", "-1")]
+    );
+}